@@ -0,0 +1,154 @@
+//! Compares the precomputed `EnglishScorer` against the original
+//! implementation, which rebuilt its lookup tables on every call, by running
+//! the single-byte XOR search over `data/4.txt`.
+//!
+//! Run with `cargo bench --bench english_score`.
+extern crate cryptopals;
+
+use cryptopals::analysis::EnglishScorer;
+use cryptopals::data;
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+const ITERATIONS: u32 = 5;
+
+/// The scorer as it was before `EnglishScorer`, kept for comparison.
+fn legacy_english_score(msg: &str) -> i32 {
+    let mut score: i32 = 0;
+
+    let mut average_frequencies: HashMap<char, f32> = HashMap::new();
+    average_frequencies.insert('e', 0.1202);
+    average_frequencies.insert('t', 0.091);
+    average_frequencies.insert('a', 0.0812);
+    average_frequencies.insert('o', 0.0768);
+    average_frequencies.insert('i', 0.0731);
+    average_frequencies.insert('n', 0.0695);
+    average_frequencies.insert('s', 0.0628);
+    average_frequencies.insert('r', 0.0602);
+    average_frequencies.insert('h', 0.0592);
+
+    let mut vowels: HashSet<char> = HashSet::new();
+    vowels.insert('a');
+    vowels.insert('e');
+    vowels.insert('i');
+    vowels.insert('o');
+    vowels.insert('u');
+
+    let mut char_counts: HashMap<char, u32> = HashMap::new();
+    let mut vowel_count: usize = 0;
+    let mut space_count: usize = 0;
+    for c in msg.chars() {
+        let c = c.to_lowercase().next().unwrap();
+        if c.is_alphabetic() {
+            *char_counts.entry(c).or_insert(0) += 1
+        }
+        if vowels.contains(&c) {
+            vowel_count += 1;
+        }
+        if c == ' ' {
+            space_count += 1;
+        }
+    }
+
+    let vowel_percent = vowel_count as f32 / (msg.len() - space_count) as f32;
+    if vowel_percent < 0.15 {
+        score -= 10;
+    } else if vowel_percent > 0.3 {
+        score += 50;
+    } else if vowel_percent > 0.2 {
+        score += 20;
+    }
+
+    if (msg.len() as f32 / space_count as f32) < 12. {
+        score += 50;
+    }
+
+    for (c, count) in &char_counts {
+        let char_percent = *count as f32 / (msg.len() - space_count) as f32;
+        if let Some(avg_freq) = average_frequencies.get(c) {
+            let rel_diff = (avg_freq - char_percent).abs() / avg_freq;
+            let delta = (1. / rel_diff).floor() as i32;
+            if char_percent > *avg_freq - 0.01 {
+                score += 10;
+            }
+            match rel_diff < 0.5 {
+                true => score += delta,
+                false => score -= delta,
+            }
+        } else if char_percent > 0.1 {
+            score -= 5;
+        }
+    }
+
+    score
+}
+
+fn load_ciphertexts() -> Vec<Vec<u8>> {
+    data::load_hex_lines("4.txt").unwrap()
+}
+
+/// Try every single-byte key on every ciphertext and return the best
+/// `(score, line, key)`.
+fn search<F: Fn(&str) -> i32>(ciphertexts: &[Vec<u8>], score: F) -> (i32, usize, u8) {
+    let mut best = (i32::MIN, 0, 0);
+    let mut decrypted = Vec::new();
+    for (line, ciphertext) in ciphertexts.iter().enumerate() {
+        for key in 0..=255u8 {
+            decrypted.clear();
+            decrypted.extend(ciphertext.iter().map(|b| b ^ key));
+            if let Ok(msg) = std::str::from_utf8(&decrypted) {
+                let s = score(msg);
+                if s > best.0 {
+                    best = (s, line, key);
+                }
+            }
+        }
+    }
+    best
+}
+
+fn time<F: FnMut() -> (i32, usize, u8)>(name: &str, mut f: F) -> (Duration, (i32, usize, u8)) {
+    let mut result = f();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        result = f();
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+    println!(
+        "{:<16} {:>10.3} ms/search",
+        name,
+        elapsed.as_secs_f64() * 1000.
+    );
+    (elapsed, result)
+}
+
+fn main() {
+    let ciphertexts = load_ciphertexts();
+    println!(
+        "single-byte XOR search over {} lines of data/4.txt, {} iterations",
+        ciphertexts.len(),
+        ITERATIONS
+    );
+
+    let (baseline_time, _) = time("no scoring", || search(&ciphertexts, |_| 0));
+    let (legacy_time, legacy_best) = time("legacy", || search(&ciphertexts, legacy_english_score));
+    let scorer = EnglishScorer::new();
+    let (scorer_time, scorer_best) = time("EnglishScorer", || {
+        search(&ciphertexts, |m| scorer.score(m))
+    });
+    assert_eq!(legacy_best, scorer_best);
+
+    let speedup = |legacy: Duration, scorer: Duration| legacy.as_secs_f64() / scorer.as_secs_f64();
+    println!(
+        "speedup: {:.1}x end to end, {:.1}x in scoring alone",
+        speedup(legacy_time, scorer_time),
+        speedup(
+            legacy_time.saturating_sub(baseline_time),
+            scorer_time.saturating_sub(baseline_time)
+        )
+    );
+    println!(
+        "best match: line {}, key {:#04x}",
+        scorer_best.1, scorer_best.2
+    );
+}
@@ -0,0 +1,533 @@
+use analysis::{infer_key_period, rank_periods_by_ioc, EnglishScorer};
+use encrypt::{
+    affine_decrypt, caesar_decrypt, mod_inverse_26, substitution_decrypt, vigenere_decrypt,
+    SubstitutionKey,
+};
+
+/// Frequencies of every letter in English text, in alphabetical order.
+const ALPHABET_FREQUENCIES: [f32; 26] = [
+    0.0817, 0.0149, 0.0278, 0.0425, 0.1270, 0.0223, 0.0202, 0.0609, 0.0697, 0.0015, 0.0077, 0.0403,
+    0.0241, 0.0675, 0.0751, 0.0193, 0.0010, 0.0599, 0.0633, 0.0906, 0.0276, 0.0098, 0.0236, 0.0015,
+    0.0197, 0.0007,
+];
+
+/// English letters from most to least common.
+const LETTERS_BY_FREQUENCY: &[u8; 26] = b"etaoinshrdlcumwfgypbvkjxqz";
+
+/// English text that `NgramScorer::new` learns letter sequences from.
+const ENGLISH_SAMPLE: &str = include_str!("../../data/english.txt");
+
+/// Weights of the trigram, bigram and single-letter estimates that are mixed
+/// to give the probability of a letter following two others.
+const NGRAM_WEIGHTS: [f32; 3] = [0.6, 0.3, 0.1];
+
+/// Number of periods, taken from the top of the index of coincidence ranking,
+/// that are tried when breaking Vigenère.
+const VIGENERE_CANDIDATE_PERIODS: usize = 4;
+
+lazy_static! {
+    static ref NGRAM_SCORER: NgramScorer = NgramScorer::new();
+}
+
+/// Scores how much text looks like English for the classical cipher solvers
+///
+/// Higher is more English-like. The solvers use an `NgramScorer` by default,
+/// which looks at the order of the letters: letter ciphers keep the spacing
+/// and letter frequencies of a plaintext largely intact, so `EnglishScorer`
+/// can't tell most of their candidates apart.
+pub trait TextScorer {
+    fn score_text(&self, text: &str) -> f32;
+
+    /// Score a sequence of alphabet indexes (`0` for `a` through `25` for
+    /// `z`). By default they are scored as lowercase text.
+    fn score_letters(&self, letters: &[u8]) -> f32 {
+        let text: String = letters.iter().map(|&c| (b'a' + c) as char).collect();
+        self.score_text(&text)
+    }
+}
+
+impl TextScorer for EnglishScorer {
+    fn score_text(&self, text: &str) -> f32 {
+        self.score(text) as f32
+    }
+}
+
+impl TextScorer for NgramScorer {
+    fn score_text(&self, text: &str) -> f32 {
+        self.score(text)
+    }
+
+    fn score_letters(&self, letters: &[u8]) -> f32 {
+        NgramScorer::score_letters(self, letters)
+    }
+}
+
+/// A recovered key with the plaintext it gives and that plaintext's score
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassicalSolution<K> {
+    pub key: K,
+    pub score: f32,
+    pub plaintext: String,
+}
+
+impl<K> ClassicalSolution<K> {
+    fn new<S: TextScorer + ?Sized>(key: K, plaintext: String, scorer: &S) -> ClassicalSolution<K> {
+        ClassicalSolution {
+            key,
+            score: scorer.score_text(&plaintext),
+            plaintext,
+        }
+    }
+}
+
+/// Return the best-scoring candidate, keeping the first of equal scores.
+fn best<K, I: Iterator<Item = ClassicalSolution<K>>>(candidates: I) -> ClassicalSolution<K> {
+    candidates
+        .fold(
+            None,
+            |best: Option<ClassicalSolution<K>>, candidate| match best {
+                Some(ref b) if b.score >= candidate.score => best,
+                _ => Some(candidate),
+            },
+        )
+        .unwrap()
+}
+
+/// The letters of `text` as alphabet indexes, ignoring case and non-letters.
+fn letter_indexes(text: &str) -> Vec<u8> {
+    text.bytes()
+        .filter(|b| b.is_ascii_alphabetic())
+        .map(|b| b.to_ascii_lowercase() - b'a')
+        .collect()
+}
+
+/// Break a Caesar cipher by trying every shift, scored with `NgramScorer`
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::break_caesar;
+///
+/// assert_eq!(break_caesar("Wkh txlfn eurzq ira mxpsv ryhu wkh odcb grj").key, 3);
+/// ```
+pub fn break_caesar(ciphertext: &str) -> ClassicalSolution<u8> {
+    break_caesar_with(ciphertext, &*NGRAM_SCORER)
+}
+
+/// Break a Caesar cipher by trying every shift, scored with `scorer`
+pub fn break_caesar_with<S: TextScorer + ?Sized>(
+    ciphertext: &str,
+    scorer: &S,
+) -> ClassicalSolution<u8> {
+    best(
+        (0..26)
+            .map(|shift| ClassicalSolution::new(shift, caesar_decrypt(ciphertext, shift), scorer)),
+    )
+}
+
+/// Break an affine cipher by trying every key `(a, b)`, scored with
+/// `NgramScorer`
+pub fn break_affine(ciphertext: &str) -> ClassicalSolution<(u8, u8)> {
+    break_affine_with(ciphertext, &*NGRAM_SCORER)
+}
+
+/// Break an affine cipher by trying every key `(a, b)`, scored with `scorer`
+pub fn break_affine_with<S: TextScorer + ?Sized>(
+    ciphertext: &str,
+    scorer: &S,
+) -> ClassicalSolution<(u8, u8)> {
+    best(
+        (1..26)
+            .filter(|&a| mod_inverse_26(a).is_some())
+            .flat_map(|a| (0..26).map(move |b| (a, b)))
+            .map(|(a, b)| {
+                ClassicalSolution::new((a, b), affine_decrypt(ciphertext, a, b).unwrap(), scorer)
+            }),
+    )
+}
+
+/// Break a Vigenère cipher with a key of at most `max_period` letters,
+/// scored with `NgramScorer`
+///
+/// The periods with the highest index of coincidence over the letters of the
+/// ciphertext are tried. Each column of a period is solved as a Caesar cipher
+/// by comparing its letter frequencies with English, and the key whose
+/// plaintext scores best wins. A key that repeats itself is reduced to its
+/// shortest period.
+///
+/// # Errors
+/// Returns an error if `max_period` is zero or the ciphertext has no letters.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::break_vigenere;
+/// use cryptopals::encrypt::vigenere_encrypt;
+///
+/// let plaintext = "It was the best of times, it was the worst of times, it was the age of \
+///     wisdom, it was the age of foolishness, it was the epoch of belief, it was the epoch \
+///     of incredulity, it was the season of Light, it was the season of Darkness";
+/// let ciphertext = vigenere_encrypt(plaintext, "dickens").unwrap();
+///
+/// assert_eq!(break_vigenere(&ciphertext, 10).unwrap().key, "dickens");
+/// ```
+pub fn break_vigenere(
+    ciphertext: &str,
+    max_period: usize,
+) -> Result<ClassicalSolution<String>, &'static str> {
+    break_vigenere_with(ciphertext, max_period, &*NGRAM_SCORER)
+}
+
+/// Break a Vigenère cipher like `break_vigenere`, scoring the candidate keys
+/// with `scorer`
+///
+/// # Errors
+/// Returns an error if `max_period` is zero or the ciphertext has no letters.
+pub fn break_vigenere_with<S: TextScorer + ?Sized>(
+    ciphertext: &str,
+    max_period: usize,
+    scorer: &S,
+) -> Result<ClassicalSolution<String>, &'static str> {
+    let letters = letter_indexes(ciphertext);
+    if letters.is_empty() {
+        return Err("ciphertext has no letters");
+    }
+
+    let periods = rank_periods_by_ioc(&letters, max_period.min(letters.len()))?;
+    Ok(best(periods.iter().take(VIGENERE_CANDIDATE_PERIODS).map(
+        |guess| {
+            let mut key: Vec<u8> = (0..guess.period)
+                .map(|column| {
+                    let column: Vec<u8> = letters
+                        .iter()
+                        .skip(column)
+                        .step_by(guess.period)
+                        .cloned()
+                        .collect();
+                    best_caesar_shift(&column)
+                })
+                .collect();
+            if let Some(period) = infer_key_period(&key) {
+                if key.len().is_multiple_of(period) {
+                    key.truncate(period);
+                }
+            }
+            let key: String = key.into_iter().map(|k| (b'a' + k) as char).collect();
+            let plaintext = vigenere_decrypt(ciphertext, &key).unwrap();
+            ClassicalSolution::new(key, plaintext, scorer)
+        },
+    )))
+}
+
+/// Return the shift that makes the letter frequencies of `column` closest to
+/// English, by the chi-squared statistic.
+fn best_caesar_shift(column: &[u8]) -> u8 {
+    let mut counts = [0u32; 26];
+    for &c in column {
+        counts[c as usize] += 1;
+    }
+    let total = column.len() as f32;
+    let chi_squared = |shift: u8| -> f32 {
+        (0..26)
+            .map(|p| {
+                let expected = ALPHABET_FREQUENCIES[p] * total;
+                let observed = counts[(p + shift as usize) % 26] as f32;
+                (observed - expected) * (observed - expected) / expected
+            })
+            .sum()
+    };
+    (0..26)
+        .min_by(|&s1, &s2| chi_squared(s1).partial_cmp(&chi_squared(s2)).unwrap())
+        .unwrap()
+}
+
+/// Scores letter sequences by their log probability under a trigram model of
+/// English
+///
+/// The probability of each letter given the two before it mixes trigram,
+/// bigram and single-letter estimates, so sequences that never occur in the
+/// training text still get a finite score. Higher is more English-like. Build
+/// one and reuse it, like `EnglishScorer`.
+pub struct NgramScorer {
+    trigram_log_probs: Vec<f32>,
+}
+
+impl NgramScorer {
+    /// Create a scorer trained on a bundled sample of English text.
+    pub fn new() -> NgramScorer {
+        NgramScorer::from_sample(ENGLISH_SAMPLE)
+    }
+
+    /// Create a scorer trained on the letters of `sample`, for example to
+    /// score another language. Single-letter probabilities come from the
+    /// sample too, so it should be at least a few thousand letters long.
+    pub fn from_sample(sample: &str) -> NgramScorer {
+        let letters = letter_indexes(sample);
+        let mut unigrams = [1f32; 26];
+        let mut bigrams = vec![0f32; 26 * 26];
+        let mut trigrams = vec![0f32; 26 * 26 * 26];
+        for &c in letters.iter() {
+            unigrams[c as usize] += 1.;
+        }
+        for bigram in letters.windows(2) {
+            bigrams[ngram_index(bigram)] += 1.;
+        }
+        for trigram in letters.windows(3) {
+            trigrams[ngram_index(trigram)] += 1.;
+        }
+        let total: f32 = unigrams.iter().sum();
+
+        let mut trigram_log_probs = vec![0f32; 26 * 26 * 26];
+        for a in 0..26 {
+            for b in 0..26 {
+                let ab = a * 26 + b;
+                let ab_count: f32 = bigrams[ab];
+                let b_count: f32 = bigrams[b * 26..(b + 1) * 26].iter().sum();
+                for c in 0..26 {
+                    let mut p = NGRAM_WEIGHTS[2] * unigrams[c] / total;
+                    if b_count > 0. {
+                        p += NGRAM_WEIGHTS[1] * bigrams[b * 26 + c] / b_count;
+                    }
+                    if ab_count > 0. {
+                        p += NGRAM_WEIGHTS[0] * trigrams[ab * 26 + c] / ab_count;
+                    }
+                    trigram_log_probs[ab * 26 + c] = p.ln();
+                }
+            }
+        }
+
+        NgramScorer { trigram_log_probs }
+    }
+
+    /// Score a sequence of alphabet indexes (`0` for `a` through `25` for `z`).
+    pub fn score_letters(&self, letters: &[u8]) -> f32 {
+        letters
+            .windows(3)
+            .map(|t| self.trigram_log_probs[ngram_index(t)])
+            .sum()
+    }
+
+    /// Score the letters of `text`, ignoring case and everything else.
+    pub fn score(&self, text: &str) -> f32 {
+        self.score_letters(&letter_indexes(text))
+    }
+}
+
+impl Default for NgramScorer {
+    fn default() -> NgramScorer {
+        NgramScorer::new()
+    }
+}
+
+/// Index of a sequence of alphabet indexes in a table of all such sequences.
+fn ngram_index(ngram: &[u8]) -> usize {
+    ngram.iter().fold(0, |index, &c| index * 26 + c as usize)
+}
+
+/// Break a monoalphabetic substitution cipher by hill climbing
+///
+/// Starting from the key that matches letter frequencies, pairs of letters are
+/// swapped for as long as a swap improves the `NgramScorer` score of the
+/// plaintext. The climb is repeated `restarts` more times, each from a random
+/// perturbation of the best key so far, and the best key found is kept. A few
+/// hundred letters of ciphertext are usually enough to recover most of the
+/// key; letters that don't occur in the ciphertext can't be recovered.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::break_substitution;
+/// use cryptopals::encrypt::{substitution_encrypt, SubstitutionKey};
+///
+/// let key = SubstitutionKey::from_alphabet("phqgiumeaylnofdxjkrcvstzwb").unwrap();
+/// let plaintext = "it was the best of times it was the worst of times it was the age of \
+///     wisdom it was the age of foolishness it was the epoch of belief it was the epoch of \
+///     incredulity it was the season of light it was the season of darkness it was the spring \
+///     of hope it was the winter of despair we had everything before us we had nothing before \
+///     us we were all going direct to heaven we were all going direct the other way";
+/// let ciphertext = substitution_encrypt(plaintext, &key);
+///
+/// assert_eq!(break_substitution(&ciphertext, 5).plaintext, plaintext);
+/// ```
+pub fn break_substitution(ciphertext: &str, restarts: usize) -> ClassicalSolution<SubstitutionKey> {
+    break_substitution_with(ciphertext, restarts, &*NGRAM_SCORER)
+}
+
+/// Break a monoalphabetic substitution cipher like `break_substitution`,
+/// scoring the candidate keys with `scorer`
+pub fn break_substitution_with<S: TextScorer + ?Sized>(
+    ciphertext: &str,
+    restarts: usize,
+    scorer: &S,
+) -> ClassicalSolution<SubstitutionKey> {
+    let letters = letter_indexes(ciphertext);
+
+    // Map the most common ciphertext letters to the most common English ones.
+    let mut counts = [0u32; 26];
+    for &c in letters.iter() {
+        counts[c as usize] += 1;
+    }
+    let mut by_frequency: Vec<u8> = (0..26).collect();
+    by_frequency.sort_by(|&a, &b| counts[b as usize].cmp(&counts[a as usize]));
+    let mut initial = [0u8; 26];
+    for (rank, &c) in by_frequency.iter().enumerate() {
+        initial[c as usize] = LETTERS_BY_FREQUENCY[rank] - b'a';
+    }
+
+    let mut rng_state: u32 = 0x2545_f491;
+    let mut next_random = move |bound: usize| -> usize {
+        rng_state ^= rng_state << 13;
+        rng_state ^= rng_state >> 17;
+        rng_state ^= rng_state << 5;
+        rng_state as usize % bound
+    };
+
+    let mut best_decryption = initial;
+    let mut best_score = f32::MIN;
+    let mut plaintext = vec![0u8; letters.len()];
+    for restart in 0..=restarts {
+        let mut decryption = if restart == 0 {
+            initial
+        } else {
+            best_decryption
+        };
+        if restart > 0 {
+            for _ in 0..4 {
+                decryption.swap(next_random(26), next_random(26));
+            }
+        }
+
+        let mut score = score_decryption(scorer, &letters, &decryption, &mut plaintext);
+        let mut improved = true;
+        while improved {
+            improved = false;
+            for i in 0..26 {
+                for j in i + 1..26 {
+                    decryption.swap(i, j);
+                    let candidate = score_decryption(scorer, &letters, &decryption, &mut plaintext);
+                    if candidate > score {
+                        score = candidate;
+                        improved = true;
+                    } else {
+                        decryption.swap(i, j);
+                    }
+                }
+            }
+        }
+
+        if score > best_score {
+            best_score = score;
+            best_decryption = decryption;
+        }
+    }
+
+    let mut forward = [0u8; 26];
+    for (c, &p) in best_decryption.iter().enumerate() {
+        forward[p as usize] = c as u8;
+    }
+    let key = SubstitutionKey::from_permutation(forward).unwrap();
+    let plaintext = substitution_decrypt(ciphertext, &key);
+    ClassicalSolution::new(key, plaintext, scorer)
+}
+
+fn score_decryption<S: TextScorer + ?Sized>(
+    scorer: &S,
+    letters: &[u8],
+    decryption: &[u8; 26],
+    plaintext: &mut Vec<u8>,
+) -> f32 {
+    plaintext.clear();
+    plaintext.extend(letters.iter().map(|&c| decryption[c as usize]));
+    scorer.score_letters(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::classical::*;
+    use encrypt::{affine_encrypt, caesar_encrypt, substitution_encrypt, vigenere_encrypt};
+
+    const PLAINTEXT: &str = "There were a king with a large jaw and a queen with a plain face, on \
+        the throne of England; there were a king with a large jaw and a queen with a fair face, \
+        on the throne of France. In both countries it was clearer than crystal to the lords of \
+        the State preserves of loaves and fishes, that things in general were settled for ever. \
+        It was the year of Our Lord one thousand seven hundred and seventy-five. Spiritual \
+        revelations were conceded to England at that favoured period, as at this.";
+
+    #[test]
+    fn test_break_caesar() {
+        for shift in 0..26 {
+            let solution = break_caesar(&caesar_encrypt(PLAINTEXT, shift));
+            assert_eq!(solution.key, shift);
+            assert_eq!(solution.plaintext, PLAINTEXT);
+        }
+
+        let ciphertext = caesar_encrypt(PLAINTEXT, 11);
+        assert_eq!(
+            break_caesar(&ciphertext).score,
+            NgramScorer::new().score(PLAINTEXT)
+        );
+        let scorer = EnglishScorer::new();
+        let solution = break_caesar_with(&ciphertext, &scorer);
+        assert_eq!(solution.key, 11);
+        assert_eq!(solution.score, scorer.score(PLAINTEXT) as f32);
+    }
+
+    #[test]
+    fn test_break_affine() {
+        let ciphertext = affine_encrypt(PLAINTEXT, 7, 19).unwrap();
+        let solution = break_affine(&ciphertext);
+        assert_eq!(solution.key, (7, 19));
+        assert_eq!(solution.plaintext, PLAINTEXT);
+        assert_eq!(
+            break_affine_with(&ciphertext, &NgramScorer::new()),
+            solution
+        );
+    }
+
+    #[test]
+    fn test_break_vigenere() {
+        let scorer = NgramScorer::new();
+        for key in ["lemon", "k", "dickens", "revolution"].iter() {
+            let ciphertext = vigenere_encrypt(PLAINTEXT, key).unwrap();
+            let solution = break_vigenere(&ciphertext, 12).unwrap();
+            assert_eq!(&solution.key, key);
+            assert_eq!(solution.plaintext, PLAINTEXT);
+            assert_eq!(break_vigenere_with(&ciphertext, 12, &scorer), Ok(solution));
+        }
+        assert!(break_vigenere("123", 12).is_err());
+        assert!(break_vigenere("abc", 0).is_err());
+    }
+
+    #[test]
+    fn test_ngram_scorer() {
+        let scorer = NgramScorer::new();
+        assert!(scorer.score("the quick brown fox") > scorer.score("xqzjv kpwbw fzgwx"));
+        assert_eq!(scorer.score("The Quick"), scorer.score("thequick"));
+        assert_eq!(scorer.score("ab"), 0.);
+    }
+
+    #[test]
+    fn test_break_substitution() {
+        let key = SubstitutionKey::from_alphabet("zebrascdfghijklmnopqtuvwxy").unwrap();
+        let ciphertext = substitution_encrypt(PLAINTEXT, &key);
+        let solution = break_substitution(&ciphertext, 10);
+
+        let correct = solution
+            .plaintext
+            .chars()
+            .zip(PLAINTEXT.chars())
+            .filter(|(c1, c2)| c1 == c2)
+            .count();
+        assert!(
+            correct as f32 >= 0.95 * PLAINTEXT.len() as f32,
+            "{}",
+            solution.plaintext
+        );
+
+        let with_scorer = break_substitution_with(&ciphertext, 10, &NgramScorer::new());
+        assert_eq!(with_scorer.plaintext, solution.plaintext);
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+
+/// Block size of AES, the cipher most often run in ECB mode.
+pub const DEFAULT_ECB_BLOCK_SIZE: usize = 16;
+
+/// A block that occurs more than once in a ciphertext
+#[derive(Debug, PartialEq)]
+pub struct RepeatedBlock {
+    pub block: Vec<u8>,
+    /// Byte offsets of every occurrence, in increasing order.
+    pub offsets: Vec<usize>,
+}
+
+/// The repeated blocks found in a ciphertext
+///
+/// ECB encrypts equal plaintext blocks to equal ciphertext blocks, so repeated
+/// blocks are a strong sign of ECB mode. Other modes repeat a block only by
+/// chance, which is vanishingly rare for 16-byte blocks.
+#[derive(Debug, PartialEq)]
+pub struct EcbReport {
+    pub block_size: usize,
+    /// Number of whole blocks in the ciphertext. Trailing bytes that don't
+    /// fill a block are ignored.
+    pub total_blocks: usize,
+    /// Blocks that occur more than once, ordered by their first offset.
+    pub repeated_blocks: Vec<RepeatedBlock>,
+}
+
+impl EcbReport {
+    /// Number of blocks that are a copy of an earlier block.
+    pub fn duplicate_count(&self) -> usize {
+        self.repeated_blocks
+            .iter()
+            .map(|r| r.offsets.len() - 1)
+            .sum()
+    }
+
+    /// Fraction of blocks that are a copy of an earlier block, from `0.0` for
+    /// no repeats to just under `1.0` for a single block repeated throughout.
+    pub fn score(&self) -> f32 {
+        match self.total_blocks {
+            0 => 0.,
+            total => self.duplicate_count() as f32 / total as f32,
+        }
+    }
+
+    pub fn is_likely_ecb(&self) -> bool {
+        !self.repeated_blocks.is_empty()
+    }
+}
+
+/// Find the repeated `block_size`-byte blocks of a ciphertext
+///
+/// # Errors
+/// Returns an error if `block_size` is zero.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::detect_ecb;
+///
+/// let ciphertext = b"YELLOW SUBMARINEyellow submarineYELLOW SUBMARINE";
+/// let report = detect_ecb(ciphertext, 16).unwrap();
+///
+/// assert!(report.is_likely_ecb());
+/// assert_eq!(report.repeated_blocks[0].offsets, vec![0, 32]);
+/// ```
+pub fn detect_ecb(ciphertext: &[u8], block_size: usize) -> Result<EcbReport, &'static str> {
+    if block_size == 0 {
+        return Err("block size must be nonzero");
+    }
+
+    let mut offsets: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for (i, block) in ciphertext.chunks_exact(block_size).enumerate() {
+        offsets.entry(block).or_default().push(i * block_size);
+    }
+
+    let mut repeated_blocks: Vec<RepeatedBlock> = offsets
+        .into_iter()
+        .filter(|(_, offsets)| offsets.len() > 1)
+        .map(|(block, offsets)| RepeatedBlock {
+            block: block.to_vec(),
+            offsets,
+        })
+        .collect();
+    repeated_blocks.sort_by_key(|r| r.offsets[0]);
+
+    Ok(EcbReport {
+        block_size,
+        total_blocks: ciphertext.len() / block_size,
+        repeated_blocks,
+    })
+}
+
+/// Rank ciphertexts, such as the lines of a file, by how likely they are to
+/// have been encrypted in ECB mode
+///
+/// Returns the index of each ciphertext with its report, most likely first.
+/// Ciphertexts with equal scores keep their original order.
+///
+/// # Errors
+/// Returns an error if `block_size` is zero.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::rank_ecb_candidates;
+///
+/// let lines: Vec<&[u8]> = vec![b"0123456789abcdef", b"0123456789abcdef0123456789abcdef"];
+/// let ranked = rank_ecb_candidates(lines, 16).unwrap();
+///
+/// assert_eq!(ranked[0].0, 1);
+/// ```
+pub fn rank_ecb_candidates<I, T>(
+    ciphertexts: I,
+    block_size: usize,
+) -> Result<Vec<(usize, EcbReport)>, &'static str>
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<[u8]>,
+{
+    let mut ranked = ciphertexts
+        .into_iter()
+        .enumerate()
+        .map(|(i, c)| detect_ecb(c.as_ref(), block_size).map(|r| (i, r)))
+        .collect::<Result<Vec<_>, _>>()?;
+    ranked.sort_by(|(_, r1), (_, r2)| r2.score().partial_cmp(&r1.score()).unwrap());
+
+    Ok(ranked)
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::ecb::*;
+
+    #[test]
+    fn test_detect_ecb() {
+        let mut ciphertext = Vec::new();
+        ciphertext.extend_from_slice(b"AAAAAAAABBBBBBBB");
+        ciphertext.extend_from_slice(b"CCCCCCCCDDDDDDDD");
+        ciphertext.extend_from_slice(b"AAAAAAAABBBBBBBB");
+        ciphertext.extend_from_slice(b"AAAAAAAABBBBBBBB");
+        ciphertext.extend_from_slice(b"CCCCCCCCDDDDDDDD");
+        ciphertext.extend_from_slice(b"AAAA");
+
+        let report = detect_ecb(&ciphertext, 16).unwrap();
+        assert_eq!(report.total_blocks, 5);
+        assert_eq!(
+            report.repeated_blocks,
+            vec![
+                RepeatedBlock {
+                    block: b"AAAAAAAABBBBBBBB".to_vec(),
+                    offsets: vec![0, 32, 48],
+                },
+                RepeatedBlock {
+                    block: b"CCCCCCCCDDDDDDDD".to_vec(),
+                    offsets: vec![16, 64],
+                },
+            ]
+        );
+        assert_eq!(report.duplicate_count(), 3);
+        assert_eq!(report.score(), 0.6);
+        assert!(report.is_likely_ecb());
+
+        // The same data in 8-byte blocks has more repeats.
+        let report = detect_ecb(&ciphertext, 8).unwrap();
+        assert_eq!(report.total_blocks, 10);
+        assert_eq!(report.duplicate_count(), 6);
+    }
+
+    #[test]
+    fn test_detect_ecb_no_repeats() {
+        let ciphertext: Vec<u8> = (0..64).collect();
+        let report = detect_ecb(&ciphertext, 16).unwrap();
+        assert!(!report.is_likely_ecb());
+        assert_eq!(report.score(), 0.);
+
+        let report = detect_ecb(b"", 16).unwrap();
+        assert_eq!(report.total_blocks, 0);
+        assert_eq!(report.score(), 0.);
+
+        assert!(detect_ecb(b"abc", 0).is_err());
+    }
+
+    #[test]
+    fn test_rank_ecb_candidates() {
+        let random: Vec<u8> = (0..48).collect();
+        let mut some_repeats = random.clone();
+        some_repeats[32..48].copy_from_slice(&random[0..16]);
+        let all_repeats = vec![7; 48];
+
+        let ranked = rank_ecb_candidates(
+            vec![&random, &some_repeats, &all_repeats, &random],
+            DEFAULT_ECB_BLOCK_SIZE,
+        )
+        .unwrap();
+        let order: Vec<usize> = ranked.iter().map(|&(i, _)| i).collect();
+        assert_eq!(order, vec![2, 1, 0, 3]);
+    }
+}
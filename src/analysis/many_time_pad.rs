@@ -0,0 +1,335 @@
+use analysis::ENGLISH_SCORER;
+use conversions::HexValue;
+use std::str;
+
+/// Breaks ciphertexts that were XORed with the same keystream, such as
+/// fixed-nonce CTR or a reused one-time pad
+///
+/// The ciphertexts are aligned by column: byte `j` of every ciphertext was
+/// XORed with keystream byte `j`, so each keystream byte is solved like a
+/// single-byte XOR key over the column, scored with `english_score`. Columns
+/// covered by few ciphertexts are unreliable; pin plaintext you know or can
+/// guess to correct them and re-solve the rest.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::ManyTimePad;
+///
+/// let keystream = [0x3c, 0x91, 0x5a, 0x07, 0xe2];
+/// let encrypt = |msg: &[u8]| -> Vec<u8> {
+///     msg.iter().zip(keystream.iter()).map(|(m, k)| m ^ k).collect()
+/// };
+///
+/// let mut pad = ManyTimePad::new(vec![encrypt(b"hello"), encrypt(b"there")]);
+/// pad.pin_plaintext(0, 0, b"hello").unwrap();
+///
+/// assert_eq!(pad.keystream(), &keystream);
+/// assert_eq!(pad.plaintexts()[1], b"there".to_vec());
+/// ```
+pub struct ManyTimePad {
+    ciphertexts: Vec<Vec<u8>>,
+    keystream: Vec<u8>,
+    pinned: Vec<bool>,
+}
+
+impl ManyTimePad {
+    /// Create a solver for `ciphertexts` and solve every keystream byte.
+    pub fn new(ciphertexts: Vec<Vec<u8>>) -> ManyTimePad {
+        let len = ciphertexts.iter().map(|c| c.len()).max().unwrap_or(0);
+        let mut pad = ManyTimePad {
+            ciphertexts,
+            keystream: vec![0; len],
+            pinned: vec![false; len],
+        };
+        pad.solve();
+        pad
+    }
+
+    /// Solve every keystream byte that hasn't been pinned.
+    ///
+    /// Keys that decrypt the whole column to printable ASCII are preferred;
+    /// among them, or among all keys that give UTF-8 if there are none, the
+    /// one with the best `english_score` wins.
+    pub fn solve(&mut self) {
+        let mut column = Vec::with_capacity(self.ciphertexts.len());
+        let mut decrypted = Vec::with_capacity(self.ciphertexts.len());
+        for offset in 0..self.keystream.len() {
+            if self.pinned[offset] {
+                continue;
+            }
+
+            column.clear();
+            column.extend(self.ciphertexts.iter().filter_map(|c| c.get(offset)));
+
+            let mut best: Option<(bool, i32, u8)> = None;
+            for key in 0..=255u8 {
+                decrypted.clear();
+                decrypted.extend(column.iter().map(|b| b ^ key));
+                if let Ok(msg) = str::from_utf8(&decrypted) {
+                    let candidate = (is_printable(&decrypted), ENGLISH_SCORER.score(msg), key);
+                    if best.is_none_or(|(printable, score, _)| {
+                        (candidate.0, candidate.1) > (printable, score)
+                    }) {
+                        best = Some(candidate);
+                    }
+                }
+            }
+            if let Some((_, _, key)) = best {
+                self.keystream[offset] = key;
+            }
+        }
+    }
+
+    /// Pin the keystream under `plaintext`, known to start `offset` bytes into
+    /// message `message`, so that solving leaves it alone.
+    ///
+    /// # Errors
+    /// Returns an error if there is no such message or if the plaintext runs
+    /// past the end of it.
+    pub fn pin_plaintext(
+        &mut self,
+        message: usize,
+        offset: usize,
+        plaintext: &[u8],
+    ) -> Result<(), &'static str> {
+        let ciphertext = self.ciphertexts.get(message).ok_or("no such message")?;
+        let covered = offset
+            .checked_add(plaintext.len())
+            .and_then(|end| ciphertext.get(offset..end))
+            .ok_or("plaintext runs past the end of the message")?;
+        for (i, (c, p)) in covered.iter().zip(plaintext.iter()).enumerate() {
+            self.keystream[offset + i] = c ^ p;
+            self.pinned[offset + i] = true;
+        }
+        Ok(())
+    }
+
+    /// Pin a single keystream byte.
+    ///
+    /// # Errors
+    /// Returns an error if `offset` is past the end of the keystream.
+    pub fn pin_keystream(&mut self, offset: usize, key: u8) -> Result<(), &'static str> {
+        if offset >= self.keystream.len() {
+            return Err("offset is past the end of the keystream");
+        }
+        self.keystream[offset] = key;
+        self.pinned[offset] = true;
+        Ok(())
+    }
+
+    /// Let solving change a pinned keystream byte again.
+    pub fn unpin(&mut self, offset: usize) {
+        if let Some(pinned) = self.pinned.get_mut(offset) {
+            *pinned = false;
+        }
+    }
+
+    pub fn is_pinned(&self, offset: usize) -> bool {
+        self.pinned.get(offset).cloned().unwrap_or(false)
+    }
+
+    pub fn ciphertexts(&self) -> &[Vec<u8>] {
+        &self.ciphertexts
+    }
+
+    /// The recovered keystream, as long as the longest ciphertext.
+    pub fn keystream(&self) -> &[u8] {
+        &self.keystream
+    }
+
+    /// Every message decrypted with the recovered keystream.
+    pub fn plaintexts(&self) -> Vec<Vec<u8>> {
+        self.ciphertexts
+            .iter()
+            .map(|c| {
+                c.iter()
+                    .zip(self.keystream.iter())
+                    .map(|(c, k)| c ^ k)
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// A placement of a crib against the XOR of two ciphertexts
+#[derive(Debug, PartialEq)]
+pub struct CribPlacement {
+    pub offset: usize,
+    /// What the other message would contain at `offset` if the crib is right.
+    pub revealed: Vec<u8>,
+    /// Whether `revealed` is entirely printable text, which makes the
+    /// placement worth a closer look.
+    pub printable: bool,
+}
+
+/// Drag `crib` across `xored`, the XOR of two ciphertexts encrypted with the
+/// same keystream
+///
+/// Wherever the crib is the plaintext of one message, XORing it into
+/// `xored` reveals the plaintext of the other message. Returns every
+/// placement where the crib fits.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::drag_crib;
+/// use cryptopals::conversions::HexValue;
+///
+/// let xored = HexValue::from_bytes(b"attack at dawn").xor(&HexValue::from_bytes(b"defend at dusk"));
+/// let placements = drag_crib(&xored, b"at d");
+///
+/// assert_eq!(placements[7].revealed, b"at d".to_vec());
+/// ```
+pub fn drag_crib(xored: &HexValue, crib: &[u8]) -> Vec<CribPlacement> {
+    if crib.is_empty() || crib.len() > xored.bytes.len() {
+        return Vec::new();
+    }
+
+    let crib = HexValue::from_bytes(crib);
+    xored
+        .bytes
+        .windows(crib.bytes.len())
+        .enumerate()
+        .map(|(offset, window)| {
+            let revealed = HexValue::from_bytes(window).xor(&crib).bytes;
+            CribPlacement {
+                offset,
+                printable: is_printable(&revealed),
+                revealed,
+            }
+        })
+        .collect()
+}
+
+fn is_printable(msg: &[u8]) -> bool {
+    msg.iter()
+        .all(|&b| (b' '..=b'~').contains(&b) || b == b'\n' || b == b'\t' || b == b'\r')
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::many_time_pad::*;
+    use analysis::xorshift_bytes;
+    use conversions::HexValue;
+
+    const MESSAGES: [&str; 20] = [
+        "It was the best of times, it was the worst of times,",
+        "it was the age of wisdom, it was the age of foolishness,",
+        "it was the epoch of belief, it was the epoch of incredulity,",
+        "it was the season of Light, it was the season of Darkness,",
+        "it was the spring of hope, it was the winter of despair,",
+        "we had everything before us, we had nothing before us,",
+        "we were all going direct to Heaven,",
+        "we were all going direct the other way - in short,",
+        "the period was so far like the present period,",
+        "that some of its noisiest authorities insisted on its being received,",
+        "for good or for evil, in the superlative degree of comparison only.",
+        "There were a king with a large jaw and a queen with a plain face,",
+        "on the throne of England; there were a king with a large jaw",
+        "and a queen with a fair face, on the throne of France.",
+        "In both countries it was clearer than crystal to the lords",
+        "of the State preserves of loaves and fishes,",
+        "that things in general were settled for ever.",
+        "It was the year of Our Lord one thousand seven hundred and seventy-five.",
+        "Spiritual revelations were conceded to England at that favoured period,",
+        "as at this. Mrs. Southcott had recently attained her five-and-twentieth",
+    ];
+
+    fn keystream(len: usize) -> Vec<u8> {
+        xorshift_bytes(0x9e37_79b9, len)
+    }
+
+    fn ciphertexts() -> Vec<Vec<u8>> {
+        let keystream = keystream(80);
+        MESSAGES
+            .iter()
+            .map(|m| {
+                m.bytes()
+                    .zip(keystream.iter())
+                    .map(|(b, k)| b ^ k)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_many_time_pad_solve() {
+        let pad = ManyTimePad::new(ciphertexts());
+        assert_eq!(pad.keystream().len(), 72);
+
+        // Columns covered by most messages should come out right.
+        let expected = keystream(80);
+        let correct = (0..40)
+            .filter(|&i| pad.keystream()[i] == expected[i])
+            .count();
+        assert!(
+            correct >= 36,
+            "only {} of 40 keystream bytes correct",
+            correct
+        );
+    }
+
+    #[test]
+    fn test_many_time_pad_pin() {
+        let unpinned = ManyTimePad::new(ciphertexts());
+        let expected = keystream(72);
+        assert_ne!(&unpinned.keystream()[47..], &expected[47..]);
+
+        // Only the last three messages reach past column 60, so the solver
+        // gets the tail wrong. Pin a crib over the tail of the longest one.
+        let mut pad = ManyTimePad::new(ciphertexts());
+        let crib = b"hundred and seventy-five.";
+        pad.pin_plaintext(17, 47, crib).unwrap();
+        assert!(!pad.is_pinned(46));
+        assert!(pad.is_pinned(47));
+        assert!(pad.is_pinned(71));
+
+        pad.solve();
+        assert_eq!(&pad.keystream()[47..], &expected[47..]);
+        assert_eq!(&pad.keystream()[..47], &unpinned.keystream()[..47]);
+
+        // The crib carries over to every other message reaching those
+        // columns.
+        let plaintexts = pad.plaintexts();
+        for (plaintext, msg) in plaintexts.iter().zip(MESSAGES.iter()) {
+            if msg.len() > 47 {
+                assert_eq!(&plaintext[47..], &msg.as_bytes()[47..]);
+            }
+        }
+
+        pad.pin_keystream(0, 0).unwrap();
+        assert_eq!(pad.keystream()[0], 0);
+        pad.unpin(0);
+        pad.solve();
+        assert_eq!(pad.keystream()[0], expected[0]);
+
+        assert!(pad.pin_plaintext(20, 0, b"a").is_err());
+        assert!(pad.pin_plaintext(6, 30, b"abcdef").is_err());
+        assert!(pad.pin_plaintext(6, usize::MAX, b"a").is_err());
+        assert!(pad.pin_keystream(72, 0).is_err());
+    }
+
+    #[test]
+    fn test_drag_crib() {
+        let c1 = HexValue::from_bytes(b"the cat sat");
+        let c2 = HexValue::from_bytes(b"a dog lay!!");
+        let placements = drag_crib(&c1.xor(&c2), b"the");
+        assert_eq!(placements.len(), 9);
+        assert_eq!(placements[0].offset, 0);
+        assert_eq!(placements[0].revealed, b"a d".to_vec());
+        assert!(placements[0].printable);
+
+        assert!(drag_crib(&c1.xor(&c2), b"").is_empty());
+        assert!(drag_crib(&c1.xor(&c2), b"a much longer crib").is_empty());
+    }
+
+    #[test]
+    fn test_many_time_pad_empty() {
+        let pad = ManyTimePad::new(vec![]);
+        assert!(pad.keystream().is_empty());
+        assert!(pad.plaintexts().is_empty());
+    }
+}
@@ -0,0 +1,492 @@
+mod classical;
+mod ecb;
+mod many_time_pad;
+mod period;
+mod stats;
+mod xor;
+
+pub use self::classical::{
+    break_affine, break_affine_with, break_caesar, break_caesar_with, break_substitution,
+    break_substitution_with, break_vigenere, break_vigenere_with, ClassicalSolution, NgramScorer,
+    TextScorer,
+};
+pub use self::ecb::{
+    detect_ecb, rank_ecb_candidates, EcbReport, RepeatedBlock, DEFAULT_ECB_BLOCK_SIZE,
+};
+pub use self::many_time_pad::{drag_crib, CribPlacement, ManyTimePad};
+pub use self::period::{
+    index_of_coincidence, kasiski_examination, periodic_index_of_coincidence,
+    rank_periods_by_hamming, rank_periods_by_ioc, KasiskiReport, PeriodGuess, RepeatedNgram,
+};
+pub use self::stats::{
+    byte_histogram, chi_squared_uniformity, printable_ratio, shannon_entropy, sliding_entropy,
+    DataClass, StatsReport,
+};
+pub use self::xor::{
+    break_single_byte_xor, infer_key_period, key_from_keystream, recover_keystream,
+    recover_repeating_key, search_single_byte_xor, SingleByteXorMatch,
+};
+
+/// Average frequencies of the most common letters in English text.
+const LETTER_FREQUENCIES: [(u8, f32); 9] = [
+    (b'e', 0.1202),
+    (b't', 0.091),
+    (b'a', 0.0812),
+    (b'o', 0.0768),
+    (b'i', 0.0731),
+    (b'n', 0.0695),
+    (b's', 0.0628),
+    (b'r', 0.0602),
+    (b'h', 0.0592),
+];
+
+const VOWELS: [u8; 5] = [b'a', b'e', b'i', b'o', b'u'];
+
+lazy_static! {
+    static ref ENGLISH_SCORER: EnglishScorer = EnglishScorer::new();
+}
+
+/// Largest reward or penalty for a single letter's frequency, reached when
+/// the letter's frequency in the message exactly matches its average.
+const MAX_LETTER_DELTA: f32 = 1000.;
+
+/// Character counts gathered from a message before scoring it.
+struct CharCounts {
+    letters: [u32; 256],
+    other_letters: u32,
+    vowels: usize,
+    spaces: usize,
+    length: usize,
+}
+
+/// Scores how much a message looks like English text
+///
+/// The lookup tables are built once, indexed by byte, so scoring a message
+/// does not allocate. Reuse a single scorer (or call `english_score`, which
+/// shares one) when scoring many candidate plaintexts.
+///
+/// Lengths and frequencies are counted in characters, not bytes. Alphabetic
+/// characters outside of ASCII count towards the message length but are
+/// scored together as foreign letters, which are penalized when they make up
+/// more than a tenth of the message.
+///
+/// Messages with nothing but spaces, including the empty message, carry no
+/// evidence either way and score `0`. Messages without spaces get no bonus
+/// for word length.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::EnglishScorer;
+///
+/// let scorer = EnglishScorer::new();
+/// assert!(scorer.score("the quick brown fox") > scorer.score("xqzv kjwp bcd"));
+/// assert_eq!(scorer.score(""), 0);
+/// ```
+pub struct EnglishScorer {
+    frequencies: [f32; 256],
+    vowels: [bool; 256],
+}
+
+impl EnglishScorer {
+    pub fn new() -> EnglishScorer {
+        let mut frequencies = [0.; 256];
+        for &(c, freq) in LETTER_FREQUENCIES.iter() {
+            frequencies[c as usize] = freq;
+        }
+
+        let mut vowels = [false; 256];
+        for &c in VOWELS.iter() {
+            vowels[c as usize] = true;
+        }
+
+        EnglishScorer {
+            frequencies,
+            vowels,
+        }
+    }
+
+    pub fn score(&self, msg: &str) -> i32 {
+        let mut counts = CharCounts::new();
+        for c in msg.chars() {
+            if c.is_ascii() {
+                self.count_ascii(&mut counts, c as u8);
+            } else {
+                counts.length += 1;
+                if c.is_alphabetic() {
+                    counts.other_letters += 1;
+                }
+            }
+        }
+        self.score_counts(&counts)
+    }
+
+    /// Score a candidate plaintext without first checking that it is UTF-8.
+    ///
+    /// Every byte counts as one character, so this agrees with `score` on
+    /// ASCII input. Bytes outside of ASCII are never letters.
+    pub fn score_bytes(&self, msg: &[u8]) -> i32 {
+        let mut counts = CharCounts::new();
+        for &c in msg {
+            self.count_ascii(&mut counts, c);
+        }
+        self.score_counts(&counts)
+    }
+
+    fn count_ascii(&self, counts: &mut CharCounts, c: u8) {
+        let c = c.to_ascii_lowercase();
+        counts.letters[c as usize] += 1;
+        counts.length += 1;
+        if self.vowels[c as usize] {
+            counts.vowels += 1;
+        }
+        if c == b' ' {
+            counts.spaces += 1;
+        }
+    }
+
+    fn score_counts(&self, counts: &CharCounts) -> i32 {
+        let non_space = counts.length - counts.spaces;
+        if non_space == 0 {
+            return 0;
+        }
+
+        let mut score: i32 = 0;
+
+        let vowel_percent = counts.vowels as f32 / non_space as f32;
+        if vowel_percent < 0.15 {
+            score -= 10;
+        } else if vowel_percent > 0.3 {
+            score += 50;
+        } else if vowel_percent > 0.2 {
+            score += 20;
+        }
+
+        if counts.spaces > 0 && (counts.length as f32 / counts.spaces as f32) < 12. {
+            score += 50;
+        }
+
+        for c in b'a'..=b'z' {
+            let count = counts.letters[c as usize];
+            if count == 0 {
+                continue;
+            }
+            let char_percent = count as f32 / non_space as f32;
+            let avg_freq = self.frequencies[c as usize];
+            if avg_freq > 0. {
+                let rel_diff = (avg_freq - char_percent).abs() / avg_freq;
+                let delta = (1. / rel_diff).min(MAX_LETTER_DELTA).floor() as i32;
+                if char_percent > avg_freq - 0.01 {
+                    score += 10;
+                }
+                match rel_diff < 0.5 {
+                    true => score += delta,
+                    false => score -= delta,
+                }
+            } else if char_percent > 0.1 {
+                score -= 5;
+            }
+        }
+
+        if counts.other_letters as f32 / non_space as f32 > 0.1 {
+            score -= 5;
+        }
+
+        score
+    }
+}
+
+impl CharCounts {
+    fn new() -> CharCounts {
+        CharCounts {
+            letters: [0; 256],
+            other_letters: 0,
+            vowels: 0,
+            spaces: 0,
+            length: 0,
+        }
+    }
+}
+
+impl Default for EnglishScorer {
+    fn default() -> EnglishScorer {
+        EnglishScorer::new()
+    }
+}
+
+/// Score how much `msg` looks like English text using a shared `EnglishScorer`
+pub fn english_score(msg: &str) -> i32 {
+    ENGLISH_SCORER.score(msg)
+}
+
+/// Return the [Hamming distance](https://en.wikipedia.org/wiki/Hamming_distance) of two strings
+///
+/// # Arguments
+///
+/// * `s1 and s2` - strings from which to compute the Hamming distance
+///
+/// # Panics
+/// The function panics if `s1` and `s2` are not the same length. Use
+/// `hamming_distance_bytes` for a non-panicking version.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::hamming_distance;
+///
+/// let distance = hamming_distance("this is a test", "wokka wokka!!!");
+///
+/// assert_eq!(distance, 37);
+/// ```
+pub fn hamming_distance(s1: &str, s2: &str) -> u32 {
+    assert_eq!(s1.len(), s2.len());
+    hamming_distance_bytes(s1.as_bytes(), s2.as_bytes()).unwrap()
+}
+
+/// Return the Hamming distance of two byte slices
+///
+/// Bits are counted eight bytes at a time with `count_ones`, with a bytewise
+/// pass over any remainder.
+///
+/// # Arguments
+///
+/// * `b1 and b2` - byte slices from which to compute the Hamming distance
+///
+/// # Errors
+/// Returns an error if `b1` and `b2` are not the same length.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::hamming_distance_bytes;
+///
+/// assert_eq!(hamming_distance_bytes(b"this is a test", b"wokka wokka!!!"), Ok(37));
+/// assert!(hamming_distance_bytes(b"abc", b"ab").is_err());
+/// ```
+pub fn hamming_distance_bytes(b1: &[u8], b2: &[u8]) -> Result<u32, &'static str> {
+    if b1.len() != b2.len() {
+        return Err("inputs are not the same length");
+    }
+
+    let mut set_bits: u32 = 0;
+    let words1 = b1.chunks_exact(8);
+    let words2 = b2.chunks_exact(8);
+    let (rem1, rem2) = (words1.remainder(), words2.remainder());
+    for (w1, w2) in words1.zip(words2) {
+        let w1 = u64::from_ne_bytes([w1[0], w1[1], w1[2], w1[3], w1[4], w1[5], w1[6], w1[7]]);
+        let w2 = u64::from_ne_bytes([w2[0], w2[1], w2[2], w2[3], w2[4], w2[5], w2[6], w2[7]]);
+        set_bits += (w1 ^ w2).count_ones();
+    }
+    for (b1, b2) in rem1.iter().zip(rem2.iter()) {
+        set_bits += (b1 ^ b2).count_ones();
+    }
+
+    Ok(set_bits)
+}
+
+/// Return the Hamming distance of two byte slices divided by their length in bytes
+///
+/// Two empty slices have a normalized distance of `0.0`.
+///
+/// # Errors
+/// Returns an error if `b1` and `b2` are not the same length.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::normalized_hamming_distance;
+///
+/// assert_eq!(normalized_hamming_distance(b"\x00\x00", b"\xff\x00"), Ok(4.));
+/// ```
+pub fn normalized_hamming_distance(b1: &[u8], b2: &[u8]) -> Result<f32, &'static str> {
+    let distance = hamming_distance_bytes(b1, b2)?;
+    match b1.len() {
+        0 => Ok(0.),
+        len => Ok(distance as f32 / len as f32),
+    }
+}
+
+/// Return the average normalized Hamming distance over all pairs of the first
+/// `num_blocks` blocks of `block_size` bytes in `data`
+///
+/// This is the statistic used to estimate the key size of repeating-key XOR:
+/// the correct key size tends to give the smallest average. No allocation is
+/// done, so it is cheap to call for every candidate key size.
+///
+/// # Errors
+/// Returns an error if `block_size` is zero, if `num_blocks` is less than two,
+/// or if `data` is shorter than `block_size * num_blocks` bytes.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::average_block_distance;
+///
+/// let data = b"abcdabcdabcd";
+/// assert_eq!(average_block_distance(data, 4, 3), Ok(0.));
+/// assert!(average_block_distance(data, 4, 4).is_err());
+/// ```
+pub fn average_block_distance(
+    data: &[u8],
+    block_size: usize,
+    num_blocks: usize,
+) -> Result<f32, &'static str> {
+    if block_size == 0 {
+        return Err("block size must be nonzero");
+    }
+    if num_blocks < 2 {
+        return Err("at least two blocks are required");
+    }
+    let too_short = "input is too short for the requested blocks";
+    if block_size
+        .checked_mul(num_blocks)
+        .is_none_or(|len| data.len() < len)
+    {
+        return Err(too_short);
+    }
+    // Half of either `num_blocks` or `num_blocks - 1` is exact.
+    let pairs = match num_blocks % 2 {
+        0 => (num_blocks / 2).checked_mul(num_blocks - 1),
+        _ => num_blocks.checked_mul((num_blocks - 1) / 2),
+    };
+    let compared = pairs
+        .and_then(|pairs| pairs.checked_mul(block_size))
+        .ok_or(too_short)?;
+
+    let mut blocks = data.chunks_exact(block_size).take(num_blocks);
+    let mut total: u64 = 0;
+    while let Some(first) = blocks.next() {
+        for second in blocks.clone() {
+            total += u64::from(hamming_distance_bytes(first, second)?);
+        }
+    }
+
+    Ok(total as f32 / compared as f32)
+}
+
+/// Deterministic pseudorandom bytes from an xorshift generator, for tests
+/// that need data without structure.
+#[cfg(test)]
+pub(crate) fn xorshift_bytes(seed: u32, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state >> 24) as u8
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::*;
+
+    #[test]
+    fn test_english_scorer() {
+        let scorer = EnglishScorer::new();
+        let english = "Cooking MC's like a pound of bacon";
+        assert_eq!(scorer.score(english), english_score(english));
+        assert_eq!(
+            scorer.score(english),
+            scorer.score_bytes(english.as_bytes())
+        );
+        assert!(scorer.score(english) > scorer.score("Dkkhjmf@IB(t@hjl@b@qkthe@kg@cbhkj"));
+        assert_eq!(scorer.score("HELLO THERE"), scorer.score("hello there"));
+    }
+
+    #[test]
+    fn test_english_scorer_empty_and_spaces() {
+        let scorer = EnglishScorer::new();
+        assert_eq!(scorer.score(""), 0);
+        assert_eq!(scorer.score("    "), 0);
+        assert_eq!(scorer.score_bytes(b""), 0);
+        assert_eq!(scorer.score_bytes(b"  "), 0);
+    }
+
+    #[test]
+    fn test_english_scorer_single_word() {
+        let scorer = EnglishScorer::new();
+        // Trailing spaces only change the word-length bonus, which a message
+        // without spaces doesn't get.
+        assert_eq!(scorer.score("strange") + 50, scorer.score("strange "));
+    }
+
+    #[test]
+    fn test_english_scorer_exact_frequency() {
+        // "e" makes up exactly its average frequency, so its relative
+        // difference is zero. The reward is capped rather than overflowing.
+        let scorer = EnglishScorer::new();
+        let msg: String = (0..10000)
+            .map(|i| match i {
+                0..=1201 => 'e',
+                1202..=1607 => 'a',
+                _ => 'x',
+            })
+            .collect();
+        assert_eq!(scorer.score(&msg), -2 + 10 + 1000 - 5);
+    }
+
+    #[test]
+    fn test_english_scorer_non_ascii() {
+        let scorer = EnglishScorer::new();
+        // Lengths are counted in characters, so accents don't change the
+        // score beyond the foreign-letter penalty.
+        assert_eq!(scorer.score("ééé ééé"), -10 + 50 - 5);
+        assert_eq!(scorer.score("caf\u{e9}"), scorer.score("cafx"));
+        // Characters whose lowercase form is several characters don't count
+        // as the first of them.
+        assert_eq!(scorer.score("\u{130}"), scorer.score("\u{e9}"));
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        assert_eq!(hamming_distance("this is a test", "wokka wokka!!!"), 37);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hamming_distance_unequal_lengths() {
+        hamming_distance("abc", "ab");
+    }
+
+    #[test]
+    fn test_hamming_distance_bytes() {
+        assert_eq!(hamming_distance_bytes(b"", b""), Ok(0));
+        assert_eq!(hamming_distance_bytes(&[0xff; 8], &[0x00; 8]), Ok(64));
+        assert_eq!(hamming_distance_bytes(&[0xff; 19], &[0x0f; 19]), Ok(76));
+        assert_eq!(
+            hamming_distance_bytes(b"this is a test", b"wokka wokka!!!"),
+            Ok(37)
+        );
+        assert!(hamming_distance_bytes(b"abc", b"ab").is_err());
+    }
+
+    #[test]
+    fn test_normalized_hamming_distance() {
+        assert_eq!(normalized_hamming_distance(b"", b""), Ok(0.));
+        assert_eq!(normalized_hamming_distance(&[0xff; 3], &[0x0f; 3]), Ok(4.));
+        assert!(normalized_hamming_distance(b"a", b"").is_err());
+    }
+
+    #[test]
+    fn test_average_block_distance() {
+        // Pairs: (0, 1) -> 8 bits, (0, 2) -> 0 bits, (1, 2) -> 8 bits, over 3 pairs of 2 bytes.
+        let data = [0x00, 0x00, 0xff, 0x00, 0x00, 0x00];
+        assert_eq!(average_block_distance(&data, 2, 3), Ok(16. / 6.));
+        // Trailing bytes beyond the requested blocks are ignored.
+        assert_eq!(average_block_distance(&data, 2, 2), Ok(4.));
+
+        assert!(average_block_distance(&data, 0, 2).is_err());
+        assert!(average_block_distance(&data, 2, 1).is_err());
+        assert!(average_block_distance(&data, 2, 4).is_err());
+        assert!(average_block_distance(&data, usize::MAX, 2).is_err());
+        assert!(average_block_distance(&data, 2, usize::MAX).is_err());
+    }
+}
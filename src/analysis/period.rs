@@ -0,0 +1,340 @@
+use analysis::average_block_distance;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// A candidate period (key length) with the score that ranked it
+///
+/// The meaning of `score` depends on the method that produced the guess; see
+/// the ranking functions.
+#[derive(Debug, PartialEq)]
+pub struct PeriodGuess {
+    pub period: usize,
+    pub score: f32,
+}
+
+/// An n-gram that occurs more than once in a ciphertext
+#[derive(Debug, PartialEq)]
+pub struct RepeatedNgram {
+    pub ngram: Vec<u8>,
+    /// Byte offsets of every occurrence, in increasing order.
+    pub offsets: Vec<usize>,
+}
+
+/// The result of a Kasiski examination
+#[derive(Debug, PartialEq)]
+pub struct KasiskiReport {
+    /// N-grams that occur more than once, ordered by their first offset.
+    pub repeated_ngrams: Vec<RepeatedNgram>,
+    /// Candidate periods, most likely first. The score is the fraction of
+    /// spacings between repeated n-grams that the period divides.
+    pub periods: Vec<PeriodGuess>,
+}
+
+/// Return the [index of coincidence](https://en.wikipedia.org/wiki/Index_of_coincidence)
+/// of `data`: the probability that two bytes drawn from it without
+/// replacement are equal
+///
+/// English letters give about 0.066 and uniformly random bytes about 0.004.
+/// Inputs shorter than two bytes have an index of `0.0`.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::index_of_coincidence;
+///
+/// assert_eq!(index_of_coincidence(b"aaaa"), 1.);
+/// assert_eq!(index_of_coincidence(b"abcd"), 0.);
+/// ```
+pub fn index_of_coincidence(data: &[u8]) -> f32 {
+    column_index_of_coincidence(data, 0, 1)
+}
+
+/// Return the average index of coincidence of the `period` columns of `data`,
+/// where column `i` holds the bytes at offsets `i`, `i + period`, ...
+///
+/// When `period` is the key length of a repeating-key cipher, each column is
+/// encrypted with a single key byte and keeps the index of its plaintext.
+///
+/// # Errors
+/// Returns an error if `period` is zero.
+pub fn periodic_index_of_coincidence(data: &[u8], period: usize) -> Result<f32, &'static str> {
+    if period == 0 {
+        return Err("period must be nonzero");
+    }
+
+    let total: f32 = (0..period)
+        .map(|column| column_index_of_coincidence(data, column, period))
+        .sum();
+    Ok(total / period as f32)
+}
+
+fn column_index_of_coincidence(data: &[u8], column: usize, period: usize) -> f32 {
+    let mut counts = [0u32; 256];
+    let mut n: u32 = 0;
+    for &b in data.iter().skip(column).step_by(period) {
+        counts[b as usize] += 1;
+        n += 1;
+    }
+    if n < 2 {
+        return 0.;
+    }
+
+    let coincidences: u64 = counts
+        .iter()
+        .map(|&c| u64::from(c) * u64::from(c.saturating_sub(1)))
+        .sum();
+    coincidences as f32 / (u64::from(n) * u64::from(n - 1)) as f32
+}
+
+/// Rank the periods `1..=max_period` by their periodic index of coincidence,
+/// highest first
+///
+/// Multiples of the true period score about as well as the period itself,
+/// and the smaller columns often put one of them slightly ahead. So the
+/// smallest divisor of the leading period that scores within
+/// `IOC_DIVISOR_TOLERANCE` of it is moved to the front.
+///
+/// # Errors
+/// Returns an error if `max_period` is zero.
+pub fn rank_periods_by_ioc(
+    data: &[u8],
+    max_period: usize,
+) -> Result<Vec<PeriodGuess>, &'static str> {
+    if max_period == 0 {
+        return Err("maximum period must be nonzero");
+    }
+
+    let mut guesses = (1..=max_period)
+        .map(|period| {
+            periodic_index_of_coincidence(data, period).map(|score| PeriodGuess { period, score })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    sort_descending(&mut guesses);
+    promote_divisor(&mut guesses);
+    Ok(guesses)
+}
+
+/// The fraction of the leading score that a divisor of the leading period
+/// must reach to be ranked first by `rank_periods_by_ioc`.
+const IOC_DIVISOR_TOLERANCE: f32 = 0.9;
+
+/// Move the smallest divisor of the leading period that scores within
+/// `IOC_DIVISOR_TOLERANCE` of it to the front, keeping the rest in order.
+fn promote_divisor(guesses: &mut [PeriodGuess]) {
+    let (leader, threshold) = match guesses.first() {
+        Some(first) => (first.period, first.score * IOC_DIVISOR_TOLERANCE),
+        None => return,
+    };
+    let divisor = guesses
+        .iter()
+        .enumerate()
+        .filter(|(_, g)| leader.is_multiple_of(g.period) && g.score >= threshold)
+        .min_by_key(|(_, g)| g.period)
+        .map(|(i, _)| i);
+    if let Some(i) = divisor {
+        guesses[..=i].rotate_right(1);
+    }
+}
+
+/// Rank the periods `1..=max_period` by the average normalized Hamming
+/// distance between their first `num_blocks` blocks, lowest (most likely)
+/// first
+///
+/// Periods too long for `num_blocks` blocks to fit in `data` are skipped.
+///
+/// # Errors
+/// Returns an error if `max_period` is zero or `num_blocks` is less than two.
+pub fn rank_periods_by_hamming(
+    data: &[u8],
+    max_period: usize,
+    num_blocks: usize,
+) -> Result<Vec<PeriodGuess>, &'static str> {
+    if max_period == 0 {
+        return Err("maximum period must be nonzero");
+    }
+    if num_blocks < 2 {
+        return Err("at least two blocks are required");
+    }
+
+    let mut guesses: Vec<PeriodGuess> = (1..=max_period)
+        .take_while(|period| {
+            period
+                .checked_mul(num_blocks)
+                .is_some_and(|len| len <= data.len())
+        })
+        .map(|period| PeriodGuess {
+            period,
+            score: average_block_distance(data, period, num_blocks).unwrap(),
+        })
+        .collect();
+    guesses.sort_by(|g1, g2| g1.score.partial_cmp(&g2.score).unwrap_or(Ordering::Equal));
+    Ok(guesses)
+}
+
+/// Run a [Kasiski examination](https://en.wikipedia.org/wiki/Kasiski_examination)
+/// of `data`
+///
+/// Repeated `ngram_len`-byte sequences are usually the same plaintext
+/// encrypted at the same key offset, so the spacings between them tend to be
+/// multiples of the period. Each period in `2..=max_period` is scored by the
+/// fraction of spacings between consecutive occurrences that it divides.
+///
+/// # Errors
+/// Returns an error if `ngram_len` is zero or `max_period` is less than two.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::kasiski_examination;
+///
+/// let report = kasiski_examination(b"abcxxabcyyyyabc", 3, 6).unwrap();
+///
+/// assert_eq!(report.repeated_ngrams[0].offsets, vec![0, 5, 12]);
+/// assert_eq!(report.periods[0].period, 5);
+/// ```
+pub fn kasiski_examination(
+    data: &[u8],
+    ngram_len: usize,
+    max_period: usize,
+) -> Result<KasiskiReport, &'static str> {
+    if ngram_len == 0 {
+        return Err("n-gram length must be nonzero");
+    }
+    if max_period < 2 {
+        return Err("maximum period must be at least two");
+    }
+
+    let mut offsets: HashMap<&[u8], Vec<usize>> = HashMap::new();
+    for (i, ngram) in data.windows(ngram_len).enumerate() {
+        offsets.entry(ngram).or_default().push(i);
+    }
+
+    let mut repeated_ngrams: Vec<RepeatedNgram> = offsets
+        .into_iter()
+        .filter(|(_, offsets)| offsets.len() > 1)
+        .map(|(ngram, offsets)| RepeatedNgram {
+            ngram: ngram.to_vec(),
+            offsets,
+        })
+        .collect();
+    repeated_ngrams.sort_by_key(|r| r.offsets[0]);
+
+    let mut spacing_count: usize = 0;
+    let mut factor_counts = vec![0usize; max_period + 1];
+    for repeat in repeated_ngrams.iter() {
+        for pair in repeat.offsets.windows(2) {
+            let spacing = pair[1] - pair[0];
+            spacing_count += 1;
+            for (period, count) in factor_counts.iter_mut().enumerate().skip(2) {
+                if spacing % period == 0 {
+                    *count += 1;
+                }
+            }
+        }
+    }
+
+    let mut periods: Vec<PeriodGuess> = factor_counts
+        .into_iter()
+        .enumerate()
+        .skip(2)
+        .map(|(period, count)| PeriodGuess {
+            period,
+            score: match spacing_count {
+                0 => 0.,
+                total => count as f32 / total as f32,
+            },
+        })
+        .collect();
+    sort_descending(&mut periods);
+
+    Ok(KasiskiReport {
+        repeated_ngrams,
+        periods,
+    })
+}
+
+/// Sort guesses by score, highest first, keeping smaller periods first on ties.
+fn sort_descending(guesses: &mut [PeriodGuess]) {
+    guesses.sort_by(|g1, g2| g2.score.partial_cmp(&g1.score).unwrap_or(Ordering::Equal));
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::period::*;
+    use encrypt::repeating_key_xor;
+
+    const PLAINTEXT: &str = "It was the best of times, it was the worst of times, it was the age \
+        of wisdom, it was the age of foolishness, it was the epoch of belief, it was the epoch \
+        of incredulity, it was the season of Light, it was the season of Darkness, it was the \
+        spring of hope, it was the winter of despair, we had everything before us, we had \
+        nothing before us, we were all going direct to Heaven, we were all going direct the \
+        other way";
+
+    fn ciphertext() -> Vec<u8> {
+        repeating_key_xor(PLAINTEXT, "LEMON").unwrap().bytes
+    }
+
+    #[test]
+    fn test_index_of_coincidence() {
+        assert_eq!(index_of_coincidence(b""), 0.);
+        assert_eq!(index_of_coincidence(b"a"), 0.);
+        assert_eq!(index_of_coincidence(b"aabb"), 4. / 12.);
+
+        let english = index_of_coincidence(PLAINTEXT.as_bytes());
+        let encrypted = index_of_coincidence(&ciphertext());
+        assert!(english > encrypted);
+    }
+
+    #[test]
+    fn test_periodic_index_of_coincidence() {
+        assert_eq!(periodic_index_of_coincidence(b"abab", 2), Ok(1.));
+        assert_eq!(periodic_index_of_coincidence(b"abab", 1), Ok(4. / 12.));
+        assert!(periodic_index_of_coincidence(b"abab", 0).is_err());
+    }
+
+    #[test]
+    fn test_rank_periods_by_ioc() {
+        let guesses = rank_periods_by_ioc(&ciphertext(), 12).unwrap();
+        assert_eq!(guesses.len(), 12);
+        assert_eq!(guesses[0].period, 5);
+        assert_eq!(guesses[1].period, 10);
+
+        // With a single-byte key, every period is a multiple of the true one.
+        let single = repeating_key_xor(PLAINTEXT, "K").unwrap().bytes;
+        assert_eq!(rank_periods_by_ioc(&single, 12).unwrap()[0].period, 1);
+        assert!(rank_periods_by_ioc(b"abc", 0).is_err());
+    }
+
+    #[test]
+    fn test_rank_periods_by_hamming() {
+        let guesses = rank_periods_by_hamming(&ciphertext(), 12, 30).unwrap();
+        assert_eq!(guesses[0].period % 5, 0);
+
+        // Periods that don't fit four blocks into six bytes are skipped.
+        let guesses = rank_periods_by_hamming(b"aaaaaa", 10, 4).unwrap();
+        assert_eq!(guesses.len(), 1);
+        assert!(rank_periods_by_hamming(b"aaaaaa", 10, usize::MAX)
+            .unwrap()
+            .is_empty());
+
+        assert!(rank_periods_by_hamming(b"abc", 0, 2).is_err());
+        assert!(rank_periods_by_hamming(b"abc", 2, 1).is_err());
+    }
+
+    #[test]
+    fn test_kasiski_examination() {
+        let report = kasiski_examination(&ciphertext(), 3, 12).unwrap();
+        assert!(!report.repeated_ngrams.is_empty());
+        assert_eq!(report.periods[0].period, 5);
+        assert_eq!(report.periods.len(), 11);
+
+        let report = kasiski_examination(b"abcdef", 3, 4).unwrap();
+        assert!(report.repeated_ngrams.is_empty());
+        assert!(report.periods.iter().all(|g| g.score == 0.));
+
+        assert!(kasiski_examination(b"abc", 0, 4).is_err());
+        assert!(kasiski_examination(b"abc", 3, 1).is_err());
+    }
+}
@@ -0,0 +1,311 @@
+/// Chi-squared statistic above which bytes are not uniformly distributed.
+///
+/// This is the 99.9th percentile of the chi-squared distribution with 255
+/// degrees of freedom.
+const CHI_SQUARED_UNIFORM_LIMIT: f32 = 330.5;
+
+/// Entropy, in bits per byte, above which data is considered random-looking.
+const HIGH_ENTROPY: f32 = 7.5;
+
+/// Printable ratio above which data is considered text.
+const TEXT_PRINTABLE_RATIO: f32 = 0.95;
+
+/// A best guess at what kind of data a blob is
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DataClass {
+    Empty,
+    /// Hex digits only, possibly with whitespace.
+    Hex,
+    /// Base64 characters only, possibly with line breaks and padding.
+    Base64,
+    /// Printable text that isn't hex or base64.
+    Text,
+    /// High entropy and uniformly distributed bytes: encrypted or random data.
+    Random,
+    /// High entropy but not uniformly distributed: typically compressed data.
+    Compressed,
+    /// Anything else, such as structured binary data.
+    Binary,
+}
+
+/// Summary statistics of a blob, for deciding how to attack it
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::{DataClass, StatsReport};
+///
+/// let report = StatsReport::from_bytes(b"Now that the party is jumping", 16).unwrap();
+///
+/// assert_eq!(report.class(), DataClass::Text);
+/// assert_eq!(report.printable_ratio, 1.);
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct StatsReport {
+    pub length: usize,
+    pub histogram: [u64; 256],
+    /// Shannon entropy of the whole input, in bits per byte.
+    pub entropy: f32,
+    /// Shannon entropy of consecutive, non-overlapping windows.
+    pub window_entropies: Vec<f32>,
+    pub printable_ratio: f32,
+    /// Chi-squared statistic of the byte counts against a uniform
+    /// distribution.
+    pub chi_squared: f32,
+}
+
+impl StatsReport {
+    /// Compute the statistics of `data`, with window entropies over windows of
+    /// `window` bytes.
+    ///
+    /// # Errors
+    /// Returns an error if `window` is zero.
+    pub fn from_bytes(data: &[u8], window: usize) -> Result<StatsReport, &'static str> {
+        let histogram = byte_histogram(data);
+        Ok(StatsReport {
+            length: data.len(),
+            histogram,
+            entropy: histogram_entropy(&histogram, data.len()),
+            window_entropies: sliding_entropy(data, window, window)?,
+            printable_ratio: printable_ratio(data),
+            chi_squared: histogram_chi_squared(&histogram, data.len()),
+        })
+    }
+
+    /// Guess what kind of data the report describes.
+    ///
+    /// Telling random from compressed data needs a few kilobytes of input;
+    /// on short inputs every byte value can't occur often enough for the
+    /// chi-squared test to be meaningful.
+    pub fn class(&self) -> DataClass {
+        if self.length == 0 {
+            return DataClass::Empty;
+        }
+
+        if self.printable_ratio >= TEXT_PRINTABLE_RATIO {
+            let only = |allowed: &dyn Fn(u8) -> bool| {
+                self.histogram
+                    .iter()
+                    .enumerate()
+                    .all(|(b, &count)| count == 0 || allowed(b as u8))
+            };
+            if only(&|b| b.is_ascii_hexdigit() || is_whitespace(b)) {
+                return DataClass::Hex;
+            }
+            if only(&|b| {
+                b.is_ascii_alphanumeric() || b == b'+' || b == b'/' || b == b'=' || is_line_break(b)
+            }) {
+                return DataClass::Base64;
+            }
+            return DataClass::Text;
+        }
+
+        if self.entropy >= HIGH_ENTROPY {
+            match self.chi_squared <= CHI_SQUARED_UNIFORM_LIMIT {
+                true => DataClass::Random,
+                false => DataClass::Compressed,
+            }
+        } else {
+            DataClass::Binary
+        }
+    }
+}
+
+/// Count the occurrences of each byte value in `data`
+pub fn byte_histogram(data: &[u8]) -> [u64; 256] {
+    let mut histogram = [0u64; 256];
+    for &b in data {
+        histogram[b as usize] += 1;
+    }
+    histogram
+}
+
+/// Return the [Shannon entropy](https://en.wikipedia.org/wiki/Entropy_(information_theory))
+/// of `data` in bits per byte, from `0.0` for a single repeated byte to `8.0`
+/// for uniformly distributed bytes
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::shannon_entropy;
+///
+/// assert_eq!(shannon_entropy(b"aaaa"), 0.);
+/// assert_eq!(shannon_entropy(b"abcd"), 2.);
+/// ```
+pub fn shannon_entropy(data: &[u8]) -> f32 {
+    histogram_entropy(&byte_histogram(data), data.len())
+}
+
+/// Return the Shannon entropy of each `window`-byte window of `data`, moving
+/// the window `step` bytes at a time
+///
+/// A final window shorter than `window` bytes is not included, so inputs
+/// shorter than a window give no entropies.
+///
+/// # Errors
+/// Returns an error if `window` or `step` is zero.
+pub fn sliding_entropy(data: &[u8], window: usize, step: usize) -> Result<Vec<f32>, &'static str> {
+    if window == 0 {
+        return Err("window must be nonzero");
+    }
+    if step == 0 {
+        return Err("step must be nonzero");
+    }
+
+    Ok(data
+        .windows(window)
+        .step_by(step)
+        .map(shannon_entropy)
+        .collect())
+}
+
+/// Return the fraction of `data` that is printable ASCII or whitespace
+///
+/// Empty input has a ratio of `0.0`.
+pub fn printable_ratio(data: &[u8]) -> f32 {
+    if data.is_empty() {
+        return 0.;
+    }
+    let printable = data
+        .iter()
+        .filter(|&&b| (b' '..=b'~').contains(&b) || is_whitespace(b))
+        .count();
+    printable as f32 / data.len() as f32
+}
+
+/// Return the chi-squared statistic of the byte counts of `data` against a
+/// uniform distribution
+///
+/// Uniformly random data gives values around 255, the degrees of freedom.
+/// Much larger values mean some bytes are more common than others. Empty
+/// input gives `0.0`.
+pub fn chi_squared_uniformity(data: &[u8]) -> f32 {
+    histogram_chi_squared(&byte_histogram(data), data.len())
+}
+
+fn is_whitespace(b: u8) -> bool {
+    b == b'\t' || b == b' ' || is_line_break(b)
+}
+
+fn is_line_break(b: u8) -> bool {
+    b == b'\n' || b == b'\r'
+}
+
+fn histogram_entropy(histogram: &[u64; 256], total: usize) -> f32 {
+    let total = total as f32;
+    histogram
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f32 / total;
+            -p * p.log2()
+        })
+        .sum::<f32>()
+        .max(0.)
+}
+
+fn histogram_chi_squared(histogram: &[u64; 256], total: usize) -> f32 {
+    if total == 0 {
+        return 0.;
+    }
+    let expected = total as f32 / 256.;
+    histogram
+        .iter()
+        .map(|&count| {
+            let diff = count as f32 - expected;
+            diff * diff / expected
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::stats::*;
+    use analysis::xorshift_bytes;
+
+    fn pseudorandom(len: usize) -> Vec<u8> {
+        xorshift_bytes(0x1234_5678, len)
+    }
+
+    #[test]
+    fn test_shannon_entropy() {
+        assert_eq!(shannon_entropy(b""), 0.);
+        assert_eq!(shannon_entropy(b"aaaa"), 0.);
+        assert_eq!(shannon_entropy(b"aabb"), 1.);
+        let all_bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(shannon_entropy(&all_bytes), 8.);
+    }
+
+    #[test]
+    fn test_sliding_entropy() {
+        assert_eq!(sliding_entropy(b"aaaaabcd", 4, 4), Ok(vec![0., 2.]));
+        assert_eq!(sliding_entropy(b"aabb", 2, 1), Ok(vec![0., 1., 0.]));
+        assert_eq!(sliding_entropy(b"aab", 4, 4), Ok(vec![]));
+        assert!(sliding_entropy(b"aab", 0, 4).is_err());
+        assert!(sliding_entropy(b"aab", 4, 0).is_err());
+    }
+
+    #[test]
+    fn test_byte_histogram() {
+        let histogram = byte_histogram(b"abca");
+        assert_eq!(histogram[b'a' as usize], 2);
+        assert_eq!(histogram[b'b' as usize], 1);
+        assert_eq!(histogram.iter().sum::<u64>(), 4);
+    }
+
+    #[test]
+    fn test_printable_ratio() {
+        assert_eq!(printable_ratio(b""), 0.);
+        assert_eq!(printable_ratio(b"ab\n\t"), 1.);
+        assert_eq!(printable_ratio(&[b'a', 0, 0x7f, 0xff]), 0.25);
+    }
+
+    #[test]
+    fn test_chi_squared_uniformity() {
+        assert_eq!(chi_squared_uniformity(b""), 0.);
+        let all_bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(chi_squared_uniformity(&all_bytes), 0.);
+        assert!(chi_squared_uniformity(&pseudorandom(65536)) < CHI_SQUARED_UNIFORM_LIMIT);
+        assert!(chi_squared_uniformity(&[0; 256]) > CHI_SQUARED_UNIFORM_LIMIT);
+    }
+
+    #[test]
+    fn test_stats_report_class() {
+        let class = |data: &[u8]| StatsReport::from_bytes(data, 256).unwrap().class();
+
+        assert_eq!(class(b""), DataClass::Empty);
+        assert_eq!(class(b"49276d206b696c6c\n696e6720"), DataClass::Hex);
+        assert_eq!(
+            class(b"SSdtIGtpbGxpbmcg\neW91ciBicmFpbiBs=="),
+            DataClass::Base64
+        );
+        assert_eq!(class(b"I'm killing your brain!"), DataClass::Text);
+        assert_eq!(class(&pseudorandom(65536)), DataClass::Random);
+
+        // Random data where the low half of the byte values is twice as
+        // likely still has high entropy, but isn't uniform.
+        let skewed: Vec<u8> = pseudorandom(65536)
+            .into_iter()
+            .map(|b| if b % 3 == 0 { b | 0x80 } else { b & 0x7f })
+            .collect();
+        assert_eq!(class(&skewed), DataClass::Compressed);
+
+        let mut structured = vec![0u8; 4096];
+        structured[100..200].copy_from_slice(&pseudorandom(100));
+        assert_eq!(class(&structured), DataClass::Binary);
+    }
+
+    #[test]
+    fn test_stats_report_windows() {
+        let mut data = vec![0u8; 512];
+        data.extend(pseudorandom(512));
+        let report = StatsReport::from_bytes(&data, 256).unwrap();
+        assert_eq!(report.length, 1024);
+        assert_eq!(report.window_entropies.len(), 4);
+        assert_eq!(report.window_entropies[0], 0.);
+        assert!(report.window_entropies[3] > 7.);
+        assert!(StatsReport::from_bytes(&data, 0).is_err());
+    }
+}
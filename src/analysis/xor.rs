@@ -0,0 +1,455 @@
+use analysis::ENGLISH_SCORER;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::str;
+use std::sync::Mutex;
+use std::thread;
+
+/// Number of ciphertexts a search thread takes from the input at a time.
+const SEARCH_BATCH_SIZE: usize = 256;
+
+/// The best single-byte XOR key found for one ciphertext
+#[derive(Debug, Clone)]
+pub struct SingleByteXorMatch {
+    /// Position of the ciphertext in the searched input.
+    pub index: usize,
+    pub key: u8,
+    pub score: i32,
+    pub plaintext: Vec<u8>,
+}
+
+impl SingleByteXorMatch {
+    /// Higher scores rank first, then earlier ciphertexts, then smaller keys.
+    fn rank(&self) -> (i32, Reverse<usize>, Reverse<u8>) {
+        (self.score, Reverse(self.index), Reverse(self.key))
+    }
+}
+
+// Equality follows the ranking so that it agrees with `Ord`; the plaintext
+// is determined by the ciphertext and key and takes no part.
+impl PartialEq for SingleByteXorMatch {
+    fn eq(&self, other: &SingleByteXorMatch) -> bool {
+        self.rank() == other.rank()
+    }
+}
+
+impl Eq for SingleByteXorMatch {}
+
+impl Ord for SingleByteXorMatch {
+    fn cmp(&self, other: &SingleByteXorMatch) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
+}
+
+impl PartialOrd for SingleByteXorMatch {
+    fn partial_cmp(&self, other: &SingleByteXorMatch) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Find the single-byte XOR key that makes `ciphertext` look most like
+/// English, scored with `english_score`
+///
+/// Keys that don't decrypt to UTF-8 are skipped, so `None` is returned if no
+/// key does. Ties go to the smaller key.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::break_single_byte_xor;
+///
+/// let ciphertext: Vec<u8> = b"Cooking MC's like a pound of bacon".iter().map(|b| b ^ 0x58).collect();
+/// let winner = break_single_byte_xor(&ciphertext).unwrap();
+///
+/// assert_eq!(winner.key, 0x58);
+/// ```
+pub fn break_single_byte_xor(ciphertext: &[u8]) -> Option<SingleByteXorMatch> {
+    let mut plaintext = Vec::with_capacity(ciphertext.len());
+    best_single_byte_xor(0, ciphertext, &mut plaintext)
+}
+
+fn best_single_byte_xor(
+    index: usize,
+    ciphertext: &[u8],
+    plaintext: &mut Vec<u8>,
+) -> Option<SingleByteXorMatch> {
+    let mut best: Option<(i32, u8)> = None;
+    for key in 0..=255u8 {
+        plaintext.clear();
+        plaintext.extend(ciphertext.iter().map(|b| b ^ key));
+        if let Ok(msg) = str::from_utf8(plaintext) {
+            let score = ENGLISH_SCORER.score(msg);
+            if best.is_none_or(|(best_score, _)| score > best_score) {
+                best = Some((score, key));
+            }
+        }
+    }
+
+    best.map(|(score, key)| SingleByteXorMatch {
+        index,
+        key,
+        score,
+        plaintext: ciphertext.iter().map(|b| b ^ key).collect(),
+    })
+}
+
+/// Find the `top_n` ciphertexts that decrypt most convincingly to English
+/// under a single-byte XOR key, searching on `num_threads` threads
+///
+/// Each ciphertext contributes at most its best key. Matches are returned
+/// best first, and the result doesn't depend on the number of threads.
+/// Ciphertexts are pulled from the iterator in batches, so only about
+/// `num_threads` batches and the current top matches are held in memory at
+/// once. A `num_threads` of zero uses the available parallelism.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::search_single_byte_xor;
+///
+/// let lines = vec![
+///     vec![0x1b, 0x37, 0x37, 0x33],
+///     b"Now that the party is jumping\n".iter().map(|b| b ^ 0x35).collect(),
+/// ];
+/// let matches = search_single_byte_xor(lines, 1, 2);
+///
+/// assert_eq!(matches[0].index, 1);
+/// assert_eq!(matches[0].key, 0x35);
+/// ```
+pub fn search_single_byte_xor<I, T>(
+    ciphertexts: I,
+    top_n: usize,
+    num_threads: usize,
+) -> Vec<SingleByteXorMatch>
+where
+    I: IntoIterator<Item = T>,
+    I::IntoIter: Send,
+    T: AsRef<[u8]> + Send,
+{
+    let num_threads = match num_threads {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    };
+    let input = Mutex::new(ciphertexts.into_iter().enumerate());
+
+    let tops: Vec<BinaryHeap<Reverse<SingleByteXorMatch>>> = thread::scope(|scope| {
+        let workers: Vec<_> = (0..num_threads)
+            .map(|_| scope.spawn(|| search_worker(&input, top_n)))
+            .collect();
+        workers
+            .into_iter()
+            .map(|worker| worker.join().unwrap())
+            .collect()
+    });
+
+    let mut top = BinaryHeap::new();
+    for worker_top in tops {
+        for candidate in worker_top {
+            push_bounded(&mut top, candidate.0, top_n);
+        }
+    }
+
+    let mut matches: Vec<SingleByteXorMatch> = top.into_iter().map(|m| m.0).collect();
+    matches.sort_by(|m1, m2| m2.cmp(m1));
+    matches
+}
+
+fn search_worker<I, T>(input: &Mutex<I>, top_n: usize) -> BinaryHeap<Reverse<SingleByteXorMatch>>
+where
+    I: Iterator<Item = (usize, T)>,
+    T: AsRef<[u8]>,
+{
+    let mut top = BinaryHeap::new();
+    let mut batch = Vec::with_capacity(SEARCH_BATCH_SIZE);
+    let mut plaintext = Vec::new();
+    loop {
+        batch.extend(input.lock().unwrap().by_ref().take(SEARCH_BATCH_SIZE));
+        if batch.is_empty() {
+            return top;
+        }
+        for (index, ciphertext) in batch.drain(..) {
+            if let Some(candidate) =
+                best_single_byte_xor(index, ciphertext.as_ref(), &mut plaintext)
+            {
+                push_bounded(&mut top, candidate, top_n);
+            }
+        }
+    }
+}
+
+/// Add `candidate` to a min-heap holding the best `limit` matches.
+fn push_bounded(
+    top: &mut BinaryHeap<Reverse<SingleByteXorMatch>>,
+    candidate: SingleByteXorMatch,
+    limit: usize,
+) {
+    if top.len() < limit {
+        top.push(Reverse(candidate));
+    } else if top.peek().is_some_and(|worst| candidate > worst.0) {
+        top.pop();
+        top.push(Reverse(candidate));
+    }
+}
+
+/// Recover the XOR keystream under `known_plaintext`, which starts `offset`
+/// bytes into `ciphertext`
+///
+/// # Errors
+/// Returns an error if the known plaintext runs past the end of the
+/// ciphertext.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::recover_keystream;
+///
+/// assert_eq!(recover_keystream(&[0x01, 0x22, 0x33], b"ab", 1), Ok(vec![0x43, 0x51]));
+/// ```
+pub fn recover_keystream(
+    ciphertext: &[u8],
+    known_plaintext: &[u8],
+    offset: usize,
+) -> Result<Vec<u8>, &'static str> {
+    let covered = offset
+        .checked_add(known_plaintext.len())
+        .and_then(|end| ciphertext.get(offset..end))
+        .ok_or("known plaintext runs past the end of the ciphertext")?;
+    Ok(covered
+        .iter()
+        .zip(known_plaintext.iter())
+        .map(|(c, p)| c ^ p)
+        .collect())
+}
+
+/// Return the smallest period with which `keystream` repeats, if it repeats
+/// at all
+///
+/// Only periods shorter than the keystream can be seen to repeat, and short
+/// keystreams can repeat by chance, so the longer the keystream the more the
+/// result can be trusted.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::infer_key_period;
+///
+/// assert_eq!(infer_key_period(b"ICEICEIC"), Some(3));
+/// assert_eq!(infer_key_period(b"ICE"), None);
+/// ```
+pub fn infer_key_period(keystream: &[u8]) -> Option<usize> {
+    (1..keystream.len()).find(|&period| {
+        keystream
+            .iter()
+            .zip(keystream[period..].iter())
+            .all(|(b1, b2)| b1 == b2)
+    })
+}
+
+/// Place the `keystream` recovered at `offset` into a repeating key of
+/// `period` bytes, aligned so that the key starts at offset zero
+///
+/// Key bytes the keystream doesn't cover are `None`.
+///
+/// # Errors
+/// Returns an error if `period` is zero or if the keystream doesn't repeat
+/// with the given period.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::key_from_keystream;
+///
+/// let key = key_from_keystream(b"EI", 2, 3).unwrap();
+///
+/// assert_eq!(key, vec![Some(b'I'), None, Some(b'E')]);
+/// ```
+pub fn key_from_keystream(
+    keystream: &[u8],
+    offset: usize,
+    period: usize,
+) -> Result<Vec<Option<u8>>, &'static str> {
+    if period == 0 {
+        return Err("period must be nonzero");
+    }
+
+    let mut key = vec![None; period];
+    for (i, &b) in keystream.iter().enumerate() {
+        let slot = &mut key[(offset % period + i) % period];
+        match *slot {
+            Some(existing) if existing != b => {
+                return Err("keystream does not repeat with the given period")
+            }
+            _ => *slot = Some(b),
+        }
+    }
+
+    Ok(key)
+}
+
+/// Recover a repeating XOR key from a ciphertext and some plaintext known to
+/// start `offset` bytes into it
+///
+/// The period is inferred from the recovered keystream with
+/// `infer_key_period`, which needs the known plaintext to cover the key more
+/// than once. `None` is returned if the keystream doesn't repeat. When the
+/// period is already known, use `recover_keystream` and `key_from_keystream`
+/// instead, which also give partial keys.
+///
+/// # Errors
+/// Returns an error if the known plaintext runs past the end of the
+/// ciphertext.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::analysis::recover_repeating_key;
+/// use cryptopals::encrypt::repeating_key_xor;
+///
+/// let ciphertext = repeating_key_xor("Burning 'em, if you ain't quick and nimble", "ICE").unwrap().bytes;
+/// let key = recover_repeating_key(&ciphertext, b"'em, if", 8).unwrap();
+///
+/// assert_eq!(key, Some(b"ICE".to_vec()));
+/// ```
+pub fn recover_repeating_key(
+    ciphertext: &[u8],
+    known_plaintext: &[u8],
+    offset: usize,
+) -> Result<Option<Vec<u8>>, &'static str> {
+    let keystream = recover_keystream(ciphertext, known_plaintext, offset)?;
+    Ok(infer_key_period(&keystream).map(|period| {
+        key_from_keystream(&keystream, offset, period)
+            .unwrap()
+            .into_iter()
+            .map(Option::unwrap)
+            .collect()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use analysis::xor::*;
+    use encrypt::repeating_key_xor;
+
+    fn single_byte_xor(msg: &[u8], key: u8) -> Vec<u8> {
+        msg.iter().map(|b| b ^ key).collect()
+    }
+
+    #[test]
+    fn test_break_single_byte_xor() {
+        let ciphertext = single_byte_xor(b"Cooking MC's like a pound of bacon", 0x58);
+        let winner = break_single_byte_xor(&ciphertext).unwrap();
+        assert_eq!(winner.key, 0x58);
+        assert_eq!(
+            winner.plaintext,
+            b"Cooking MC's like a pound of bacon".to_vec()
+        );
+
+        // A lone byte is only UTF-8 when it is ASCII, so only keys that clear
+        // the top bit of 0xe1 qualify.
+        let winner = break_single_byte_xor(&[0xe1]).unwrap();
+        assert!(winner.key >= 0x80);
+    }
+
+    #[test]
+    fn test_search_single_byte_xor() {
+        let mut lines: Vec<Vec<u8>> = (0..1000u32)
+            .map(|i| (0..30).map(|j| (i * 31 + j * 17) as u8 | 0x80).collect())
+            .collect();
+        lines[123] = single_byte_xor(b"Now that the party is jumping\n", 0x35);
+        lines[777] = single_byte_xor(b"Cooking MC's like a pound of bacon", 0x58);
+
+        let single = search_single_byte_xor(lines.iter(), 2, 1);
+        let parallel = search_single_byte_xor(lines.iter(), 2, 4);
+        assert_eq!(single, parallel);
+
+        let mut found: Vec<(usize, u8)> = single.iter().map(|m| (m.index, m.key)).collect();
+        found.sort();
+        assert_eq!(found, vec![(123, 0x35), (777, 0x58)]);
+        assert!(single[0].score >= single[1].score);
+
+        assert_eq!(search_single_byte_xor(lines.iter(), 10, 0).len(), 10);
+        assert!(search_single_byte_xor(lines.iter(), 0, 2).is_empty());
+        assert!(search_single_byte_xor(Vec::<Vec<u8>>::new(), 5, 2).is_empty());
+    }
+
+    #[test]
+    fn test_recover_keystream() {
+        let ciphertext = [0x10, 0x20, 0x30, 0x40];
+        assert_eq!(recover_keystream(&ciphertext, b"", 4), Ok(vec![]));
+        assert_eq!(
+            recover_keystream(&ciphertext, &[0x11, 0x22], 2),
+            Ok(vec![0x21, 0x62])
+        );
+        assert!(recover_keystream(&ciphertext, &[0x11, 0x22], 3).is_err());
+        assert!(recover_keystream(&ciphertext, b"", 5).is_err());
+        assert!(recover_keystream(&ciphertext, b"ab", usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_infer_key_period() {
+        assert_eq!(infer_key_period(b""), None);
+        assert_eq!(infer_key_period(b"aa"), Some(1));
+        assert_eq!(infer_key_period(b"abcab"), Some(3));
+        assert_eq!(infer_key_period(b"abcabd"), None);
+    }
+
+    #[test]
+    fn test_key_from_keystream() {
+        assert_eq!(
+            key_from_keystream(b"EICEI", 2, 3),
+            Ok(vec![Some(b'I'), Some(b'C'), Some(b'E')])
+        );
+        assert_eq!(key_from_keystream(b"", 0, 2), Ok(vec![None, None]));
+        assert_eq!(
+            key_from_keystream(b"CE", usize::MAX, 3),
+            Ok(vec![Some(b'C'), Some(b'E'), None])
+        );
+        assert!(key_from_keystream(b"EICXI", 2, 3).is_err());
+        assert!(key_from_keystream(b"CE", 2, 0).is_err());
+    }
+
+    #[test]
+    fn test_recover_repeating_key() {
+        let msg = "GIF89a\x01\x00\x01\x00 and the rest of the image";
+        let ciphertext = repeating_key_xor(msg, "SECRET").unwrap().bytes;
+
+        // The header alone doesn't cover the key twice, so the period can't
+        // be seen.
+        assert_eq!(recover_repeating_key(&ciphertext, b"GIF89a", 0), Ok(None));
+        assert_eq!(
+            recover_repeating_key(&ciphertext, &msg.as_bytes()[..12], 0),
+            Ok(Some(b"SECRET".to_vec()))
+        );
+        assert_eq!(
+            recover_repeating_key(&ciphertext, b" and the rest", 10),
+            Ok(Some(b"SECRET".to_vec()))
+        );
+        assert!(recover_repeating_key(&ciphertext, b"GIF89a", 100).is_err());
+    }
+
+    #[test]
+    fn test_match_eq_agrees_with_ord() {
+        use std::cmp::Ordering;
+
+        let a = SingleByteXorMatch {
+            index: 3,
+            key: 0x20,
+            score: 10,
+            plaintext: b"abc".to_vec(),
+        };
+        let mut b = a.clone();
+        b.plaintext = b"xyz".to_vec();
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert!(a == b);
+
+        b.key = 0x21;
+        assert_eq!(a.cmp(&b), Ordering::Greater);
+        assert!(a != b);
+    }
+}
@@ -0,0 +1,54 @@
+//! Error reporting and exit codes shared by the command-line tools.
+//!
+//! Each tool exits with 0 on success, 1 if an input could not be processed
+//! and 2 on a usage error, so it can be used in shell pipelines.
+use std::io::{self, Write};
+use std::process;
+
+/// An error and the exit code it should produce.
+pub struct Failure {
+    pub message: String,
+    pub code: i32,
+}
+
+impl Failure {
+    pub fn usage<S: Into<String>>(message: S) -> Failure {
+        Failure {
+            message: message.into(),
+            code: 2,
+        }
+    }
+
+    pub fn runtime<S: Into<String>>(message: S) -> Failure {
+        Failure {
+            message: message.into(),
+            code: 1,
+        }
+    }
+}
+
+/// Write the output of tool `name` to stdout, or report its failure on
+/// stderr, followed by `usage` for a usage error, and exit with its code.
+pub fn finish<T: AsRef<[u8]>>(name: &str, usage: &str, result: Result<T, Failure>) {
+    match result {
+        Ok(out) => {
+            let stdout = io::stdout();
+            let mut stdout = stdout.lock();
+            // A closed pipe (e.g. output piped into `head`) is not an error.
+            match stdout.write_all(out.as_ref()).and_then(|_| stdout.flush()) {
+                Err(ref e) if e.kind() != io::ErrorKind::BrokenPipe => {
+                    eprintln!("{}: writing output: {}", name, e);
+                    process::exit(1);
+                }
+                _ => {}
+            }
+        }
+        Err(failure) => {
+            match failure.code {
+                2 => eprintln!("{}: {}\n\n{}", name, failure.message, usage),
+                _ => eprintln!("{}: {}", name, failure.message),
+            }
+            process::exit(failure.code);
+        }
+    }
+}
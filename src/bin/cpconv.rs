@@ -0,0 +1,150 @@
+//! Encoding and XOR conversions on stdin/stdout.
+//!
+//! Usage: `cpconv <command> [options]`; run `cpconv help` for the commands.
+//! Exits with 0 on success, 1 if the input could not be processed and 2 on a
+//! usage error, so it can be used in shell pipelines.
+extern crate cryptopals;
+
+mod common;
+
+use common::Failure;
+use cryptopals::conversions::{hexdump, Encoding};
+use cryptopals::encrypt::repeating_key_xor_bytes;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+
+const USAGE: &str = "usage: cpconv <command> [options]
+
+commands:
+  hex-encode              encode stdin as hex
+  hex-decode              decode hex on stdin
+  base64-encode           encode stdin as base64
+  base64-decode           decode base64 on stdin
+  xor [options] FILE      XOR stdin with FILE, which must have the same length
+  xor-key [options] KEY   XOR stdin with KEY repeated to its length
+  hexdump                 show stdin as offsets, hex and ASCII
+  help                    show this message
+
+options for xor and xor-key:
+  -i, --input FMT         encoding of stdin and FILE (default raw)
+  -o, --output FMT        encoding of the output (default raw)
+  -k, --key FMT           encoding of KEY (default raw)
+
+FMT is one of raw, hex or base64. Whitespace in hex and base64 input is
+ignored, and hex and base64 output ends with a newline.";
+
+struct XorOptions {
+    input: Encoding,
+    output: Encoding,
+    key: Encoding,
+    operand: String,
+}
+
+impl XorOptions {
+    fn parse(args: &[String]) -> Result<XorOptions, Failure> {
+        let mut options = XorOptions {
+            input: Encoding::Raw,
+            output: Encoding::Raw,
+            key: Encoding::Raw,
+            operand: String::new(),
+        };
+        let mut operand = None;
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let target = match arg.as_str() {
+                "-i" | "--input" => &mut options.input,
+                "-o" | "--output" => &mut options.output,
+                "-k" | "--key" => &mut options.key,
+                _ if operand.is_none() => {
+                    operand = Some(arg.clone());
+                    continue;
+                }
+                _ => return Err(Failure::usage(format!("unexpected argument {}", arg))),
+            };
+            let name = args
+                .next()
+                .ok_or_else(|| Failure::usage(format!("{} needs an encoding", arg)))?;
+            *target =
+                Encoding::from_str(name).map_err(|e| Failure::usage(format!("{}: {}", name, e)))?;
+        }
+        options.operand = operand.ok_or_else(|| Failure::usage("missing operand"))?;
+        Ok(options)
+    }
+}
+
+fn read_stdin() -> Result<Vec<u8>, Failure> {
+    let mut input = Vec::new();
+    io::stdin()
+        .read_to_end(&mut input)
+        .map_err(|e| Failure::runtime(format!("reading stdin: {}", e)))?;
+    Ok(input)
+}
+
+fn decode(encoding: Encoding, input: &[u8], what: &str) -> Result<Vec<u8>, Failure> {
+    encoding
+        .decode(input)
+        .map_err(|e| Failure::runtime(format!("{}: {}", what, e)))
+}
+
+/// Encode `bytes` for output, ending text encodings with a newline.
+fn encode(encoding: Encoding, bytes: &[u8]) -> Vec<u8> {
+    let mut out = encoding.encode(bytes);
+    if encoding != Encoding::Raw {
+        out.push(b'\n');
+    }
+    out
+}
+
+fn xor(args: &[String]) -> Result<Vec<u8>, Failure> {
+    let options = XorOptions::parse(args)?;
+    let left = decode(options.input, &read_stdin()?, "stdin")?;
+    let right = fs::read(&options.operand)
+        .map_err(|e| Failure::runtime(format!("{}: {}", options.operand, e)))?;
+    let right = decode(options.input, &right, &options.operand)?;
+    if left.len() != right.len() {
+        return Err(Failure::runtime(format!(
+            "inputs have different lengths ({} and {} bytes)",
+            left.len(),
+            right.len()
+        )));
+    }
+    let out: Vec<u8> = left.iter().zip(right.iter()).map(|(a, b)| a ^ b).collect();
+    Ok(encode(options.output, &out))
+}
+
+fn xor_key(args: &[String]) -> Result<Vec<u8>, Failure> {
+    let options = XorOptions::parse(args)?;
+    let key = decode(options.key, options.operand.as_bytes(), "key")?;
+    let input = decode(options.input, &read_stdin()?, "stdin")?;
+    let out = repeating_key_xor_bytes(&input, &key).map_err(Failure::usage)?;
+    Ok(encode(options.output, &out))
+}
+
+fn run(args: &[String]) -> Result<Vec<u8>, Failure> {
+    let command = args.first().map(|a| a.as_str());
+    let rest = if args.is_empty() { args } else { &args[1..] };
+    let simple = match command {
+        Some("xor") => return xor(rest),
+        Some("xor-key") => return xor_key(rest),
+        Some("help") | Some("--help") | Some("-h") => return Ok(format!("{}\n", USAGE).into()),
+        Some(command) => command,
+        None => return Err(Failure::usage("missing command")),
+    };
+    if !rest.is_empty() {
+        return Err(Failure::usage(format!("{} takes no arguments", simple)));
+    }
+    match simple {
+        "hex-encode" => Ok(encode(Encoding::Hex, &read_stdin()?)),
+        "hex-decode" => Ok(decode(Encoding::Hex, &read_stdin()?, "stdin")?),
+        "base64-encode" => Ok(encode(Encoding::Base64, &read_stdin()?)),
+        "base64-decode" => Ok(decode(Encoding::Base64, &read_stdin()?, "stdin")?),
+        "hexdump" => Ok(hexdump(&read_stdin()?).into_bytes()),
+        _ => Err(Failure::usage(format!("unknown command {}", simple))),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    common::finish("cpconv", USAGE, run(&args));
+}
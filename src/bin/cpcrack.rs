@@ -0,0 +1,258 @@
+//! Analysis of ciphertext files from the command line.
+//!
+//! Usage: `cpcrack [options] <command> [FILE...]`; run `cpcrack help` for the
+//! commands. Exits with 0 on success, 1 if an input could not be read or
+//! analyzed and 2 on a usage error.
+extern crate cryptopals;
+
+mod common;
+
+use common::Failure;
+use cryptopals::analysis::{
+    english_score, hamming_distance_bytes, normalized_hamming_distance, rank_periods_by_hamming,
+    search_single_byte_xor,
+};
+use cryptopals::conversions::Encoding;
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+
+const USAGE: &str = "usage: cpcrack [options] <command> [FILE...]
+
+commands:
+  score [FILE]            score how much the input looks like English
+  single-xor [FILE]       rank the lines of the input by how English they
+                          become when XORed with a single byte
+  hamming FILE FILE       count the bits that differ between two inputs
+  keysize [FILE]          rank repeating-key XOR key sizes by the normalized
+                          Hamming distance between blocks
+  help                    show this message
+
+options:
+  -i, --input FMT         encoding of the input: raw (default), hex or base64;
+                          single-xor decodes each line separately
+  -n, --top N             number of results to show (default 5)
+  --max N                 largest key size to try (default 40)
+  --blocks N              blocks to compare per key size (default 4)
+  --json                  print results as JSON
+
+A FILE of `-` or no FILE reads stdin.";
+
+struct Options {
+    command: String,
+    files: Vec<String>,
+    input: Encoding,
+    top: usize,
+    max_keysize: usize,
+    blocks: usize,
+    json: bool,
+}
+
+impl Options {
+    fn parse(args: &[String]) -> Result<Options, Failure> {
+        let mut options = Options {
+            command: String::new(),
+            files: Vec::new(),
+            input: Encoding::Raw,
+            top: 5,
+            max_keysize: 40,
+            blocks: 4,
+            json: false,
+        };
+        let mut positional = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            let target = match arg.as_str() {
+                "--json" => {
+                    options.json = true;
+                    continue;
+                }
+                "-i" | "--input" => {
+                    let name = option_value(arg, args.next())?;
+                    options.input = Encoding::from_str(name)
+                        .map_err(|e| Failure::usage(format!("{}: {}", name, e)))?;
+                    continue;
+                }
+                "-n" | "--top" => &mut options.top,
+                "--max" => &mut options.max_keysize,
+                "--blocks" => &mut options.blocks,
+                _ => {
+                    positional.push(arg.clone());
+                    continue;
+                }
+            };
+            let value = option_value(arg, args.next())?;
+            *target = value
+                .parse()
+                .map_err(|_| Failure::usage(format!("{} needs a number, not {}", arg, value)))?;
+        }
+        if positional.is_empty() {
+            return Err(Failure::usage("missing command"));
+        }
+        options.command = positional.remove(0);
+        options.files = positional;
+        Ok(options)
+    }
+
+    /// Read and decode the file at `path`, or stdin if it is `-`.
+    fn read(&self, path: &str) -> Result<Vec<u8>, Failure> {
+        self.input
+            .decode(&read_raw(path)?)
+            .map_err(|e| Failure::runtime(format!("{}: {}", path, e)))
+    }
+
+    /// The single input file of a command, which defaults to stdin.
+    fn single_file(&self) -> Result<&str, Failure> {
+        match self.files.len() {
+            0 => Ok("-"),
+            1 => Ok(&self.files[0]),
+            _ => Err(Failure::usage(format!(
+                "{} takes at most one file",
+                self.command
+            ))),
+        }
+    }
+}
+
+fn option_value<'a>(option: &str, value: Option<&'a String>) -> Result<&'a str, Failure> {
+    value
+        .map(|v| v.as_str())
+        .ok_or_else(|| Failure::usage(format!("{} needs a value", option)))
+}
+
+fn read_raw(path: &str) -> Result<Vec<u8>, Failure> {
+    let mut data = Vec::new();
+    let result = match path {
+        "-" => io::stdin().read_to_end(&mut data).map(|_| ()),
+        _ => fs::read(path).map(|d| data = d),
+    };
+    result.map_err(|e| Failure::runtime(format!("{}: {}", path, e)))?;
+    Ok(data)
+}
+
+/// Quote `s` as a JSON string.
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn score(options: &Options) -> Result<String, Failure> {
+    let data = options.read(options.single_file()?)?;
+    let score = english_score(&String::from_utf8_lossy(&data));
+    Ok(match options.json {
+        true => format!("{{\"score\": {}}}", score),
+        false => format!("{}", score),
+    })
+}
+
+fn single_xor(options: &Options) -> Result<String, Failure> {
+    let path = options.single_file()?;
+    let data = read_raw(path)?;
+    let mut line_numbers = Vec::new();
+    let mut lines = Vec::new();
+    for (i, line) in data.split(|&b| b == b'\n').enumerate() {
+        if line.is_empty() {
+            continue;
+        }
+        let line = options
+            .input
+            .decode(line)
+            .map_err(|e| Failure::runtime(format!("{}:{}: {}", path, i + 1, e)))?;
+        line_numbers.push(i + 1);
+        lines.push(line);
+    }
+
+    let matches = search_single_byte_xor(lines, options.top, 0);
+    let results: Vec<String> = matches
+        .iter()
+        .map(|m| {
+            let line = line_numbers[m.index];
+            let plaintext = String::from_utf8_lossy(&m.plaintext);
+            match options.json {
+                true => format!(
+                    "{{\"line\": {}, \"key\": {}, \"score\": {}, \"plaintext\": {}}}",
+                    line,
+                    m.key,
+                    m.score,
+                    json_string(&plaintext)
+                ),
+                false => format!(
+                    "line {:<5} key 0x{:02x}  score {:<6} {:?}",
+                    line, m.key, m.score, plaintext
+                ),
+            }
+        })
+        .collect();
+    Ok(join_results(&results, options.json))
+}
+
+fn hamming(options: &Options) -> Result<String, Failure> {
+    if options.files.len() != 2 {
+        return Err(Failure::usage("hamming takes two files"));
+    }
+    let first = options.read(&options.files[0])?;
+    let second = options.read(&options.files[1])?;
+    let distance = hamming_distance_bytes(&first, &second).map_err(Failure::runtime)?;
+    let normalized = normalized_hamming_distance(&first, &second).map_err(Failure::runtime)?;
+    Ok(match options.json {
+        true => format!(
+            "{{\"distance\": {}, \"normalized\": {}}}",
+            distance, normalized
+        ),
+        false => format!("{} bits ({:.3} per byte)", distance, normalized),
+    })
+}
+
+fn keysize(options: &Options) -> Result<String, Failure> {
+    let data = options.read(options.single_file()?)?;
+    let guesses = rank_periods_by_hamming(&data, options.max_keysize, options.blocks)
+        .map_err(Failure::usage)?;
+    let results: Vec<String> = guesses
+        .iter()
+        .take(options.top)
+        .map(|g| match options.json {
+            true => format!("{{\"keysize\": {}, \"distance\": {}}}", g.period, g.score),
+            false => format!("keysize {:<4} distance {:.3}", g.period, g.score),
+        })
+        .collect();
+    Ok(join_results(&results, options.json))
+}
+
+/// Join result lines, as a JSON array if `json` is set.
+fn join_results(results: &[String], json: bool) -> String {
+    match json {
+        true if results.is_empty() => String::from("[]"),
+        true => format!("[\n  {}\n]", results.join(",\n  ")),
+        false => results.join("\n"),
+    }
+}
+
+fn run(args: &[String]) -> Result<String, Failure> {
+    let options = Options::parse(args)?;
+    match options.command.as_str() {
+        "score" => score(&options),
+        "single-xor" => single_xor(&options),
+        "hamming" => hamming(&options),
+        "keysize" => keysize(&options),
+        "help" | "--help" | "-h" => Ok(String::from(USAGE)),
+        command => Err(Failure::usage(format!("unknown command {}", command))),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    common::finish("cpcrack", USAGE, run(&args).map(|out| out + "\n"));
+}
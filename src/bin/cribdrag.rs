@@ -0,0 +1,190 @@
+//! Interactive crib dragging for ciphertexts encrypted with a reused keystream.
+//!
+//! Usage: `cribdrag <file>`, where the file holds one hex ciphertext per line.
+//! Type `help` at the prompt for the commands.
+extern crate cryptopals;
+
+use cryptopals::analysis::{drag_crib, ManyTimePad};
+use cryptopals::conversions::HexValue;
+use cryptopals::data;
+use std::env;
+use std::fs;
+use std::io::prelude::*;
+use std::io::{self, BufRead};
+use std::process;
+
+const HELP: &str = "commands:
+  drag <i> <j> <crib>      drag a crib for message i across the XOR of messages i and j
+  place <i> <offset> <crib>  accept a crib as the plaintext of message i at offset
+  key <offset> <hex byte>  pin a single keystream byte
+  unpin <offset> [len]     forget pinned keystream bytes
+  auto                     toggle showing statistically solved bytes
+  show                     show every message's plaintext
+  help                     show this message
+  quit                     exit";
+
+struct Session {
+    pad: ManyTimePad,
+    show_solved: bool,
+}
+
+impl Session {
+    fn run(&mut self, line: &str) -> Result<bool, String> {
+        let mut words = line.trim_start().splitn(2, ' ');
+        let command = words.next().unwrap_or("");
+        let rest = words.next().unwrap_or("");
+        match command {
+            "" => {}
+            "drag" => {
+                let (i, rest) = parse_number(rest)?;
+                let (j, crib) = parse_number(rest)?;
+                self.drag(i, j, crib.as_bytes())?;
+            }
+            "place" => {
+                let (i, rest) = parse_number(rest)?;
+                let (offset, crib) = parse_number(rest)?;
+                self.pad.pin_plaintext(i, offset, crib.as_bytes())?;
+                self.show();
+            }
+            "key" => {
+                let (offset, byte) = parse_number(rest)?;
+                let byte = HexValue::from_str(byte.trim())?;
+                if byte.bytes.len() != 1 {
+                    return Err(String::from("key must be a single hex byte"));
+                }
+                self.pad.pin_keystream(offset, byte.bytes[0])?;
+                self.show();
+            }
+            "unpin" => {
+                let (offset, len) = parse_number(rest)?;
+                let len = match len.trim() {
+                    "" => 1,
+                    len => len
+                        .parse()
+                        .map_err(|_| format!("invalid length: {}", len))?,
+                };
+                let end = offset
+                    .checked_add(len)
+                    .ok_or("length is too large")?
+                    .min(self.pad.keystream().len());
+                for offset in offset..end {
+                    self.pad.unpin(offset);
+                }
+                self.pad.solve();
+                self.show();
+            }
+            "auto" => {
+                self.show_solved = !self.show_solved;
+                self.show();
+            }
+            "show" => self.show(),
+            "help" => println!("{}", HELP),
+            "quit" | "exit" => return Ok(false),
+            _ => return Err(format!("unknown command: {} (try help)", command)),
+        }
+        Ok(true)
+    }
+
+    fn drag(&self, i: usize, j: usize, crib: &[u8]) -> Result<(), String> {
+        let ciphertexts = self.pad.ciphertexts();
+        let (c1, c2) = match (ciphertexts.get(i), ciphertexts.get(j)) {
+            (Some(c1), Some(c2)) => (c1, c2),
+            _ => return Err(String::from("no such message")),
+        };
+        let len = c1.len().min(c2.len());
+        let xored = HexValue::from_bytes(&c1[..len]).xor(&HexValue::from_bytes(&c2[..len]));
+
+        for placement in drag_crib(&xored, crib) {
+            println!(
+                "{:>4} {} {}",
+                placement.offset,
+                if placement.printable { '*' } else { ' ' },
+                display(&placement.revealed)
+            );
+        }
+        Ok(())
+    }
+
+    fn show(&self) {
+        let plaintexts = self.pad.plaintexts();
+        for (i, plaintext) in plaintexts.iter().enumerate() {
+            let shown: String = plaintext
+                .iter()
+                .enumerate()
+                .map(
+                    |(offset, &b)| match self.show_solved || self.pad.is_pinned(offset) {
+                        true => display_byte(b),
+                        false => '_',
+                    },
+                )
+                .collect();
+            println!("{:>4} {}", i, shown);
+        }
+        println!("key  {}", HexValue::from_bytes(self.pad.keystream()));
+    }
+}
+
+fn parse_number(args: &str) -> Result<(usize, &str), String> {
+    let args = args.trim_start();
+    let end = args.find(' ').unwrap_or(args.len());
+    let number = args[..end]
+        .parse()
+        .map_err(|_| format!("expected a number, found {:?}", &args[..end]))?;
+    let rest = match end < args.len() {
+        true => &args[end + 1..],
+        false => "",
+    };
+    Ok((number, rest))
+}
+
+fn display(bytes: &[u8]) -> String {
+    bytes.iter().map(|&b| display_byte(b)).collect()
+}
+
+fn display_byte(b: u8) -> char {
+    match b {
+        b' '..=b'~' => b as char,
+        _ => '.',
+    }
+}
+
+fn load(path: &str) -> Result<Vec<Vec<u8>>, String> {
+    let contents = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    data::parse_hex_lines(&contents).map_err(|e| format!("{}: {}", path, e))
+}
+
+fn main() {
+    let path = match env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: cribdrag <file of hex ciphertexts>");
+            process::exit(2);
+        }
+    };
+    let ciphertexts = load(&path).unwrap_or_else(|e| {
+        eprintln!("cribdrag: {}", e);
+        process::exit(1);
+    });
+
+    let mut session = Session {
+        pad: ManyTimePad::new(ciphertexts),
+        show_solved: false,
+    };
+    println!("{}\n", HELP);
+    session.show();
+
+    let stdin = io::stdin();
+    loop {
+        print!("> ");
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap() == 0 {
+            break;
+        }
+        match session.run(line.trim_end_matches(['\n', '\r'])) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => println!("error: {}", e),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+//! Solutions to the [Cryptopals](https://cryptopals.com) challenges. Each
+//! challenge returns its result so that it can be checked as well as printed.
+pub mod set1;
+pub mod set2;
+pub mod set3;
+
+use std::fmt;
+
+/// A Cryptopals challenge that can be run and checked
+pub trait Challenge {
+    fn set(&self) -> u32;
+
+    /// The challenge number, counted across all sets as on cryptopals.com.
+    fn number(&self) -> u32;
+
+    fn title(&self) -> &'static str;
+
+    /// Run the challenge and return its result in printable form.
+    fn run(&self) -> String;
+
+    /// The result a correct solution gives, in the same form as `run`.
+    fn expected(&self) -> String;
+}
+
+/// A challenge backed by a function, for challenges that need no state.
+struct FnChallenge {
+    set: u32,
+    number: u32,
+    title: &'static str,
+    run: fn() -> String,
+    expected: &'static str,
+}
+
+impl Challenge for FnChallenge {
+    fn set(&self) -> u32 {
+        self.set
+    }
+
+    fn number(&self) -> u32 {
+        self.number
+    }
+
+    fn title(&self) -> &'static str {
+        self.title
+    }
+
+    fn run(&self) -> String {
+        (self.run)()
+    }
+
+    fn expected(&self) -> String {
+        String::from(self.expected)
+    }
+}
+
+/// Return every challenge, in order.
+pub fn registry() -> Vec<Box<dyn Challenge>> {
+    let challenges = vec![
+        FnChallenge {
+            set: 1,
+            number: 1,
+            title: "Convert hex to base64",
+            run: || set1::challenge1().to_string(),
+            expected: "SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtlIGEgcG9pc29ub3VzIG11c2hyb29t",
+        },
+        FnChallenge {
+            set: 1,
+            number: 2,
+            title: "Fixed XOR",
+            run: || set1::challenge2().to_string(),
+            expected: "746865206b696420646f6e277420706c6179",
+        },
+        FnChallenge {
+            set: 1,
+            number: 3,
+            title: "Single-byte XOR cipher",
+            run: || String::from_utf8_lossy(&set1::challenge3().plaintext).into_owned(),
+            expected: "Cooking MC's like a pound of bacon",
+        },
+        FnChallenge {
+            set: 1,
+            number: 4,
+            title: "Detect single-character XOR",
+            run: || String::from_utf8_lossy(&set1::challenge4().plaintext).into_owned(),
+            expected: "Now that the party is jumping\n",
+        },
+        FnChallenge {
+            set: 1,
+            number: 5,
+            title: "Implement repeating-key XOR",
+            run: || set1::challenge5().to_string(),
+            expected: "0b3637272a2b2e63622c2e69692a23693a2a3c6324202d623d63343c2a26226324272765272a282b2f20430a652e2c652a3124333a653e2b2027630c692b20283165286326302e27282f",
+        },
+        FnChallenge {
+            set: 1,
+            number: 7,
+            title: "AES in ECB mode",
+            run: || first_line(&set1::challenge7()),
+            expected: "I'm back and I'm ringin' the bell ",
+        },
+        FnChallenge {
+            set: 2,
+            number: 9,
+            title: "Implement PKCS#7 padding",
+            run: || String::from_utf8_lossy(&set2::challenge9()).into_owned(),
+            expected: "YELLOW SUBMARINE\x04\x04\x04\x04",
+        },
+        FnChallenge {
+            set: 2,
+            number: 10,
+            title: "Implement CBC mode",
+            run: || first_line(&set2::challenge10()),
+            expected: "I'm back and I'm ringin' the bell ",
+        },
+        FnChallenge {
+            set: 2,
+            number: 15,
+            title: "PKCS#7 padding validation",
+            run: || {
+                set2::challenge15()
+                    .iter()
+                    .map(|result| match *result {
+                        Ok(ref plaintext) => String::from_utf8_lossy(plaintext).into_owned(),
+                        Err(e) => e.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            },
+            expected: "ICE ICE BABY; inconsistent padding; inconsistent padding",
+        },
+        FnChallenge {
+            set: 3,
+            number: 18,
+            title: "Implement CTR, the stream cipher mode",
+            run: || String::from_utf8_lossy(&set3::challenge18()).into_owned(),
+            expected: "Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby ",
+        },
+    ];
+
+    challenges
+        .into_iter()
+        .map(|c| Box::new(c) as Box<dyn Challenge>)
+        .collect()
+}
+
+/// The first line of a decrypted text, for challenges whose plaintext is too
+/// long to print.
+fn first_line(plaintext: &[u8]) -> String {
+    String::from_utf8_lossy(plaintext)
+        .lines()
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Which challenges to run
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Selector {
+    All,
+    Set(u32),
+    /// A set and a challenge number within it, counted across all sets.
+    Challenge(u32, u32),
+}
+
+impl Selector {
+    /// Parse a selector: `all`, a set such as `set2`, or a challenge such as
+    /// `1.4`, where the number after the dot is the challenge number as on
+    /// cryptopals.com.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate cryptopals;
+    /// use cryptopals::challenges::Selector;
+    ///
+    /// assert_eq!(Selector::from_str("set2"), Ok(Selector::Set(2)));
+    /// assert_eq!(Selector::from_str("2.9"), Ok(Selector::Challenge(2, 9)));
+    /// assert!(Selector::from_str("2.x").is_err());
+    /// ```
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(val: &str) -> Result<Selector, &'static str> {
+        let number = |s: &str| s.parse::<u32>().map_err(|_| "invalid challenge selector");
+        if val == "all" {
+            Ok(Selector::All)
+        } else if let Some(set) = val.strip_prefix("set") {
+            Ok(Selector::Set(number(set)?))
+        } else if let Some(dot) = val.find('.') {
+            Ok(Selector::Challenge(
+                number(&val[..dot])?,
+                number(&val[dot + 1..])?,
+            ))
+        } else {
+            Err("invalid challenge selector")
+        }
+    }
+
+    pub fn matches(&self, challenge: &dyn Challenge) -> bool {
+        match *self {
+            Selector::All => true,
+            Selector::Set(set) => challenge.set() == set,
+            Selector::Challenge(set, number) => {
+                challenge.set() == set && challenge.number() == number
+            }
+        }
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Selector::All => write!(f, "all"),
+            Selector::Set(set) => write!(f, "set{}", set),
+            Selector::Challenge(set, number) => write!(f, "{}.{}", set, number),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use challenges::*;
+
+    #[test]
+    fn test_registry() {
+        let challenges = registry();
+        assert_eq!(challenges[0].number(), 1);
+        for pair in challenges.windows(2) {
+            assert!(pair[0].number() < pair[1].number());
+            assert!(pair[0].set() <= pair[1].set());
+        }
+    }
+
+    #[test]
+    fn test_selector_from_str() {
+        assert_eq!(Selector::from_str("all"), Ok(Selector::All));
+        assert_eq!(Selector::from_str("set1"), Ok(Selector::Set(1)));
+        assert_eq!(Selector::from_str("1.4"), Ok(Selector::Challenge(1, 4)));
+        assert!(Selector::from_str("").is_err());
+        assert!(Selector::from_str("set").is_err());
+        assert!(Selector::from_str("1.").is_err());
+        assert!(Selector::from_str("4").is_err());
+    }
+
+    #[test]
+    fn test_selector_matches() {
+        let challenges = registry();
+        let count = |selector: Selector| {
+            challenges
+                .iter()
+                .filter(|c| selector.matches(c.as_ref()))
+                .count()
+        };
+        assert_eq!(count(Selector::All), challenges.len());
+        assert_eq!(count(Selector::Set(1)), 6);
+        assert_eq!(count(Selector::Set(2)), 3);
+        assert_eq!(count(Selector::Set(3)), 1);
+        assert_eq!(count(Selector::Set(4)), 0);
+        assert_eq!(count(Selector::Challenge(1, 4)), 1);
+        assert_eq!(count(Selector::Challenge(2, 4)), 0);
+    }
+
+    #[test]
+    fn test_selector_display() {
+        for selector in ["all", "set2", "1.4"].iter() {
+            assert_eq!(&Selector::from_str(selector).unwrap().to_string(), selector);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use analysis::{break_single_byte_xor, search_single_byte_xor, SingleByteXorMatch};
+use conversions::{Base64Value, HexValue};
+use data;
+use encrypt::{ecb_decrypt, repeating_key_xor, Aes};
+
+pub fn challenge1() -> Base64Value {
+    // https://cryptopals.com/sets/1/challenges/1
+    let hex = HexValue::from_str("49276d206b696c6c696e6720796f757220627261696e206c696b65206120706f69736f6e6f7573206d757368726f6f6d").unwrap();
+    hex.to_base64()
+}
+
+pub fn challenge2() -> HexValue {
+    // https://cryptopals.com/sets/1/challenges/2
+    let hex1 = HexValue::from_str("1c0111001f010100061a024b53535009181c").unwrap();
+    let hex2 = HexValue::from_str("686974207468652062756c6c277320657965").unwrap();
+    hex1.xor(&hex2)
+}
+
+pub fn challenge3() -> SingleByteXorMatch {
+    // https://cryptopals.com/sets/1/challenges/3
+    let encrypted =
+        HexValue::from_str("1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736")
+            .unwrap();
+    break_single_byte_xor(&encrypted.bytes).unwrap()
+}
+
+pub fn challenge4() -> SingleByteXorMatch {
+    // https://cryptopals.com/sets/1/challenges/4
+    let lines = data::load_hex_lines("4.txt").unwrap_or_else(|e| panic!("{}", e));
+
+    search_single_byte_xor(lines, 1, 0).remove(0)
+}
+
+pub fn challenge5() -> HexValue {
+    // https://cryptopals.com/sets/1/challenges/5
+    let msg = r"Burning 'em, if you ain't quick and nimble
+I go crazy when I hear a cymbal";
+    repeating_key_xor(msg, "ICE").unwrap()
+}
+
+pub fn challenge7() -> Vec<u8> {
+    // https://cryptopals.com/sets/1/challenges/7
+    let ciphertext = data::load_base64("7.txt").unwrap_or_else(|e| panic!("{}", e));
+    let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+    ecb_decrypt(&aes, &ciphertext).unwrap()
+}
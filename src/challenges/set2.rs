@@ -0,0 +1,27 @@
+use data;
+use encrypt::{cbc_decrypt, pkcs7_pad, pkcs7_unpad, Aes, PaddingError, AES_BLOCK_SIZE};
+
+pub fn challenge9() -> Vec<u8> {
+    // https://cryptopals.com/sets/2/challenges/9
+    pkcs7_pad(b"YELLOW SUBMARINE", 20)
+}
+
+pub fn challenge10() -> Vec<u8> {
+    // https://cryptopals.com/sets/2/challenges/10
+    let ciphertext = data::load_base64("10.txt").unwrap_or_else(|e| panic!("{}", e));
+    let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+    cbc_decrypt(&aes, &[0; AES_BLOCK_SIZE], &ciphertext).unwrap()
+}
+
+pub fn challenge15() -> Vec<Result<Vec<u8>, PaddingError>> {
+    // https://cryptopals.com/sets/2/challenges/15
+    let inputs: [&[u8]; 3] = [
+        b"ICE ICE BABY\x04\x04\x04\x04",
+        b"ICE ICE BABY\x05\x05\x05\x05",
+        b"ICE ICE BABY\x01\x02\x03\x04",
+    ];
+    inputs
+        .iter()
+        .map(|input| pkcs7_unpad(input, 16).map(|p| p.to_vec()))
+        .collect()
+}
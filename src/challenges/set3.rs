@@ -0,0 +1,16 @@
+use conversions::Base64Value;
+use encrypt::{Aes, CounterLayout, Ctr};
+
+pub fn challenge18() -> Vec<u8> {
+    // https://cryptopals.com/sets/3/challenges/18
+    let ciphertext = Base64Value::from_wrapped_str(
+        "L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==",
+    )
+    .unwrap();
+    let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+    let ctr = Ctr::new(aes, CounterLayout::LittleEndian64(0)).unwrap();
+
+    let mut plaintext = ciphertext.as_bytes().to_vec();
+    ctr.apply(0, &mut plaintext);
+    plaintext
+}
@@ -7,16 +7,20 @@ pub struct HexValue {
 }
 
 impl HexValue {
-    pub fn from_bytes(bytes: &Vec<u8>) -> HexValue {
+    pub fn from_bytes(bytes: &[u8]) -> HexValue {
         HexValue {
-            bytes: bytes.clone(),
+            bytes: bytes.to_vec(),
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(val: &str) -> Result<HexValue, &'static str> {
         // Verify that the input is hex.
         let val = val.to_lowercase();
-        match val.chars().map(|c| HexValue::is_hex_char(c)).all(|b| b) {
+        if !val.len().is_multiple_of(2) {
+            return Err("value has an odd number of digits");
+        }
+        match val.chars().all(HexValue::is_hex_char) {
             true => Ok(HexValue {
                 bytes: Self::to_bytes(&val),
             }),
@@ -25,10 +29,7 @@ impl HexValue {
     }
 
     fn is_hex_char(c: char) -> bool {
-        match c {
-            'a'...'f' | '0'...'9' => true,
-            _ => false,
-        }
+        matches!(c, 'a'..='f' | '0'..='9')
     }
 
     fn hex_to_byte(hex: char) -> u8 {
@@ -81,18 +82,6 @@ impl HexValue {
         )
     }
 
-    fn to_string(&self) -> String {
-        let mut out = String::with_capacity(self.bytes.len() * 2);
-        for b in self.bytes.iter() {
-            out.push_str(&format!(
-                "{}{}",
-                Self::nibble_to_hex((b & 0xf0) >> 4),
-                Self::nibble_to_hex(b & 0x0f)
-            ));
-        }
-        out
-    }
-
     pub fn to_base64(&self) -> Base64Value {
         Base64Value::from_bytes(&self.bytes)
     }
@@ -118,7 +107,15 @@ impl Eq for HexValue {}
 
 impl fmt::Display for HexValue {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        for b in self.bytes.iter() {
+            write!(
+                f,
+                "{}{}",
+                Self::nibble_to_hex((b & 0xf0) >> 4),
+                Self::nibble_to_hex(b & 0x0f)
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -139,55 +136,76 @@ pub struct Base64Value {
 }
 
 impl Base64Value {
-    pub fn from_bytes(bytes: &Vec<u8>) -> Base64Value {
+    pub fn from_bytes(bytes: &[u8]) -> Base64Value {
         Base64Value {
-            bytes: bytes.clone(),
+            bytes: bytes.to_vec(),
         }
     }
 
+    /// Decode standard base64 as defined in RFC 4648, with `=` padding
+    ///
+    /// Missing padding is accepted. Use `from_wrapped_str` for base64 that
+    /// contains whitespace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate cryptopals;
+    /// use cryptopals::conversions::Base64Value;
+    ///
+    /// let b64 = Base64Value::from_str("SGVsbG8=").unwrap();
+    ///
+    /// assert_eq!(b64.as_bytes(), b"Hello");
+    /// ```
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(val: &str) -> Result<Base64Value, &'static str> {
-        // Verify that the input is base 64.
-        match val.chars().map(|c| Self::is_base64_char(c)).all(|b| b) {
-            true => {
-                let mut bytes: Vec<u8> = vec![0; ((val.len() as f32) * 6. / 8.).ceil() as usize];
-                let mut curr_byte = bytes.len();
-                for (i, c) in val.chars().rev().enumerate() {
-                    // A base 64 character is six bits of information. In filling a byte vector,
-                    // there's four cases:
-                    //   - We are starting on a new byte, so we fill the lowest six bits.
-                    //   - Six bits in the current byte are filled, so we fill two in it
-                    //     and four in the next byte.
-                    //   - Four bits in the current byte are filled, so we fill the remaining
-                    //     four and then two in the next byte.
-                    //   - Two bits in the current byte are filled, so we fill the remaining
-                    //     six.
-                    match i % 4 {
-                        0 => {
-                            curr_byte -= 1;
-                            bytes[curr_byte] |= Self::char_to_byte(c)
-                        }
-                        1 => {
-                            let byte = Self::char_to_byte(c);
-                            bytes[curr_byte] |= (byte & 0x03) << 6;
-                            curr_byte -= 1;
-                            bytes[curr_byte] |= (byte & 0xfc) >> 2;
-                        }
-                        2 => {
-                            let byte = Self::char_to_byte(c);
-                            bytes[curr_byte] |= (byte & 0x0f) << 4;
-                            curr_byte -= 1;
-                            bytes[curr_byte] |= (byte & 0xf0) >> 4;
-                        }
-                        _ => {
-                            // 3
-                            bytes[curr_byte] |= Self::char_to_byte(c) << 2;
-                        }
-                    }
-                }
-                Ok(Base64Value { bytes: bytes })
+        let chars: Vec<char> = val.chars().collect();
+        let data_len = chars.iter().position(|&c| c == '=').unwrap_or(chars.len());
+        let padding = chars.len() - data_len;
+        if chars[data_len..].iter().any(|&c| c != '=') {
+            return Err("padding is followed by other characters");
+        }
+        if padding > 2 || (padding > 0 && !chars.len().is_multiple_of(4)) || data_len % 4 == 1 {
+            return Err("value has an invalid length");
+        }
+        if !chars[..data_len].iter().all(|&c| Self::is_base64_char(c)) {
+            return Err("value contains illegal characters");
+        }
+
+        let mut bytes = Vec::with_capacity(data_len * 3 / 4);
+        for group in chars[..data_len].chunks(4) {
+            let mut bits: u32 = 0;
+            for (i, &c) in group.iter().enumerate() {
+                bits |= u32::from(Self::char_to_byte(c)) << (18 - 6 * i);
+            }
+            for i in 0..group.len() - 1 {
+                bytes.push((bits >> (16 - 8 * i)) as u8);
             }
-            false => Err("value contains illegal characters"),
         }
+
+        Ok(Base64Value { bytes })
+    }
+
+    /// Decode base64 like `from_str`, ignoring whitespace such as the line
+    /// breaks of wrapped base64 files
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate cryptopals;
+    /// use cryptopals::conversions::Base64Value;
+    ///
+    /// let b64 = Base64Value::from_wrapped_str("SGVs\nbG8=\n").unwrap();
+    ///
+    /// assert_eq!(b64.as_bytes(), b"Hello");
+    /// ```
+    pub fn from_wrapped_str(val: &str) -> Result<Base64Value, &'static str> {
+        let unwrapped: String = val.chars().filter(|c| !c.is_whitespace()).collect();
+        Base64Value::from_str(&unwrapped)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
     }
 
     fn is_base64_char(c: char) -> bool {
@@ -199,27 +217,27 @@ impl Base64Value {
     }
 
     fn byte_to_char(byte: u8) -> char {
-        return BASE64_ALPHABET.chars().nth(byte as usize).unwrap();
+        BASE64_ALPHABET.chars().nth(byte as usize).unwrap()
     }
 
-    fn to_string(&self) -> String {
-        let mut segments: Vec<u32> = vec![0; ((self.bytes.len() as f32) / 3.0).ceil() as usize];
-
-        for (i, byte) in self.bytes.iter().rev().enumerate() {
-            let byte: u32 = *byte as u32;
-            segments[i / 3] |= byte << 8 * (i % 3);
-        }
-
-        let mut chars: Vec<char> = Vec::with_capacity(segments.len() * 4);
-        for segment in segments {
+    /// Encode as standard base64, padding the last group with `=`.
+    fn encode(&self) -> String {
+        let mut out = String::with_capacity(self.bytes.len().div_ceil(3) * 4);
+        for group in self.bytes.chunks(3) {
+            let mut segment: u32 = 0;
+            for (i, byte) in group.iter().enumerate() {
+                segment |= u32::from(*byte) << (16 - 8 * i);
+            }
             for shift in 0..4 {
-                chars.push(Base64Value::byte_to_char(
-                    ((segment >> 6 * shift) & 0x3f) as u8,
-                ));
+                match shift <= group.len() {
+                    true => out.push(Base64Value::byte_to_char(
+                        ((segment >> (18 - 6 * shift)) & 0x3f) as u8,
+                    )),
+                    false => out.push('='),
+                }
             }
         }
-
-        chars.into_iter().rev().collect()
+        out
     }
 }
 
@@ -233,8 +251,108 @@ impl Eq for Base64Value {}
 
 impl fmt::Display for Base64Value {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.to_string())
+        write!(f, "{}", self.encode())
+    }
+}
+
+/// How bytes are written as text on the command line and in data files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Raw,
+    Hex,
+    Base64,
+}
+
+impl Encoding {
+    /// Parse an encoding name: `raw`, `hex` or `base64`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(name: &str) -> Result<Encoding, &'static str> {
+        match name {
+            "raw" => Ok(Encoding::Raw),
+            "hex" => Ok(Encoding::Hex),
+            "base64" | "b64" => Ok(Encoding::Base64),
+            _ => Err("unknown encoding, expected raw, hex or base64"),
+        }
+    }
+
+    /// Decode `input` to bytes. Whitespace is ignored in hex and base64 input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// extern crate cryptopals;
+    /// use cryptopals::conversions::Encoding;
+    ///
+    /// assert_eq!(Encoding::Hex.decode(b"49 27\n6d\n").unwrap(), b"I'm");
+    /// assert_eq!(Encoding::Base64.decode(b"SSdt\n").unwrap(), b"I'm");
+    /// assert_eq!(Encoding::Raw.decode(b"I'm").unwrap(), b"I'm");
+    /// assert!(Encoding::Hex.decode(b"49 2").is_err());
+    /// ```
+    pub fn decode(self, input: &[u8]) -> Result<Vec<u8>, &'static str> {
+        if self == Encoding::Raw {
+            return Ok(input.to_vec());
+        }
+        let text = std::str::from_utf8(input).map_err(|_| "value contains illegal characters")?;
+        match self {
+            Encoding::Hex => {
+                let digits: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+                Ok(HexValue::from_str(&digits)?.bytes)
+            }
+            Encoding::Base64 => Ok(Base64Value::from_wrapped_str(text)?.bytes),
+            Encoding::Raw => unreachable!(),
+        }
+    }
+
+    /// Encode `bytes`. Hex and base64 output is a single line without a
+    /// trailing newline.
+    pub fn encode(self, bytes: &[u8]) -> Vec<u8> {
+        match self {
+            Encoding::Raw => bytes.to_vec(),
+            Encoding::Hex => HexValue::from_bytes(bytes).to_string().into_bytes(),
+            Encoding::Base64 => Base64Value::from_bytes(bytes).to_string().into_bytes(),
+        }
+    }
+}
+
+/// Format `bytes` like `hexdump -C`: offset, sixteen hex bytes split in two
+/// groups of eight, and the printable ASCII characters.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::conversions::hexdump;
+///
+/// assert_eq!(
+///     hexdump(b"Hello, world!\n"),
+///     "00000000  48 65 6c 6c 6f 2c 20 77  6f 72 6c 64 21 0a        |Hello, world!.|\n\
+///      0000000e\n"
+/// );
+/// ```
+pub fn hexdump(bytes: &[u8]) -> String {
+    let mut out = String::new();
+    for (line, chunk) in bytes.chunks(16).enumerate() {
+        out.push_str(&format!("{:08x} ", line * 16));
+        for i in 0..16 {
+            if i % 8 == 0 {
+                out.push(' ');
+            }
+            match chunk.get(i) {
+                Some(b) => out.push_str(&format!("{:02x} ", b)),
+                None => out.push_str("   "),
+            }
+        }
+        out.push_str(" |");
+        for &b in chunk {
+            out.push(match b {
+                0x20..=0x7e => b as char,
+                _ => '.',
+            });
+        }
+        out.push_str("|\n");
     }
+    out.push_str(&format!("{:08x}\n", bytes.len()));
+    out
 }
 
 #[cfg(test)]
@@ -243,7 +361,7 @@ mod tests {
 
     #[test]
     fn test_hexvalue_from_bytes() {
-        let hex = HexValue::from_bytes(&vec![171, 205]);
+        let hex = HexValue::from_bytes(&[171, 205]);
         assert_eq!(hex.bytes, vec![171, 205]);
 
         let hex = HexValue::from_bytes(&vec![
@@ -272,6 +390,9 @@ mod tests {
         let hex = HexValue::from_str("abch");
         assert!(hex.is_err());
 
+        let hex = HexValue::from_str("abc");
+        assert!(hex.is_err());
+
         let hex =
             HexValue::from_str("000102030405060708090a0b0c0d0e0ff0f1f2f3f4f5f6f7f8f9fafbfcfdfeff");
         assert!(hex.is_ok());
@@ -332,7 +453,7 @@ mod tests {
 
     #[test]
     fn test_hexvalue_to_string() {
-        assert_eq!(HexValue::from_bytes(&vec![171, 205]).to_string(), "abcd");
+        assert_eq!(HexValue::from_bytes(&[171, 205]).to_string(), "abcd");
 
         let hex = HexValue::from_bytes(&vec![
             0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 240, 241, 242, 243, 244, 245,
@@ -347,14 +468,15 @@ mod tests {
     #[test]
     fn test_hexvalue_to_base64() {
         let hex = HexValue::from_str("0f").unwrap();
-        assert_eq!(hex.to_base64(), Base64Value::from_str("P").unwrap());
+        assert_eq!(hex.to_base64(), Base64Value::from_str("Dw==").unwrap());
 
         let hex = HexValue::from_str("49276d206b696c6c696e6720796f757220627261696e206c696b65206120706f69736f6e6f7573206d757368726f6f6d").unwrap();
         assert_eq!(
             hex.to_base64(),
             Base64Value::from_str(
                 "SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtlIGEgcG9pc29ub3VzIG11c2hyb29t"
-            ).unwrap()
+            )
+            .unwrap()
         );
     }
 
@@ -372,7 +494,7 @@ mod tests {
 
     #[test]
     fn test_base64_from_bytes() {
-        let b64 = Base64Value::from_bytes(&vec![171, 205]);
+        let b64 = Base64Value::from_bytes(&[171, 205]);
         assert_eq!(b64.bytes, vec![171, 205]);
 
         let b64 = Base64Value::from_bytes(&vec![
@@ -396,6 +518,18 @@ mod tests {
 
         let b64 = Base64Value::from_str("AgN*");
         assert!(b64.is_err());
+
+        let b64 = Base64Value::from_str("TWFu\nTWE=");
+        assert!(b64.is_err());
+    }
+
+    #[test]
+    fn test_base64_round_trip() {
+        for len in 0..8 {
+            let bytes: Vec<u8> = (0xf8..0xf8 + len).collect();
+            let b64 = Base64Value::from_bytes(&bytes);
+            assert_eq!(Base64Value::from_str(&b64.to_string()), Ok(b64));
+        }
     }
 
     #[test]
@@ -431,7 +565,57 @@ mod tests {
 
     #[test]
     fn test_base64_to_string() {
-        let b64 = Base64Value::from_bytes(&vec![2, 3, 120]);
+        let b64 = Base64Value::from_bytes(&[2, 3, 120]);
         assert_eq!(b64.to_string(), "AgN4");
+
+        assert_eq!(Base64Value::from_bytes(b"").to_string(), "");
+        assert_eq!(Base64Value::from_bytes(b"M").to_string(), "TQ==");
+        assert_eq!(Base64Value::from_bytes(b"Ma").to_string(), "TWE=");
+        assert_eq!(
+            Base64Value::from_bytes(b"Hello world").to_string(),
+            "SGVsbG8gd29ybGQ="
+        );
+    }
+
+    #[test]
+    fn test_base64_from_wrapped_str() {
+        let decode = |val| Base64Value::from_wrapped_str(val).map(|b| b.bytes);
+        assert_eq!(decode(""), Ok(vec![]));
+        assert_eq!(decode("TWFu"), Ok(b"Man".to_vec()));
+        assert_eq!(decode("TWE="), Ok(b"Ma".to_vec()));
+        assert_eq!(decode("TQ=="), Ok(b"M".to_vec()));
+        assert_eq!(decode("TQ"), Ok(b"M".to_vec()));
+        assert_eq!(decode("TWFu\r\nTWE=\n"), Ok(b"ManMa".to_vec()));
+
+        assert!(decode("T").is_err());
+        assert!(decode("TQ=").is_err());
+        assert!(decode("T===").is_err());
+        assert!(decode("TQ==TQ==").is_err());
+        assert!(decode("TW*u").is_err());
+    }
+
+    #[test]
+    fn test_encoding_round_trip() {
+        let data: Vec<u8> = (0..=255).collect();
+        for &encoding in &[Encoding::Raw, Encoding::Hex, Encoding::Base64] {
+            let encoded = encoding.encode(&data);
+            assert_eq!(encoding.decode(&encoded).unwrap(), data);
+        }
+        assert_eq!(Encoding::from_str("b64"), Ok(Encoding::Base64));
+        assert!(Encoding::from_str("binary").is_err());
+        assert!(Encoding::Base64.decode(b"SGVsbG8=x").is_err());
+        assert!(Encoding::Hex.decode(b"\xff\xfe").is_err());
+    }
+
+    #[test]
+    fn test_hexdump() {
+        assert_eq!(hexdump(b""), "00000000\n");
+        let data: Vec<u8> = (0x60..0x71).collect();
+        assert_eq!(
+            hexdump(&data),
+            "00000000  60 61 62 63 64 65 66 67  68 69 6a 6b 6c 6d 6e 6f  |`abcdefghijklmno|\n\
+             00000010  70                                                |p|\n\
+             00000011\n"
+        );
     }
 }
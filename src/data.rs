@@ -0,0 +1,136 @@
+//! Loading of the challenge data files.
+//!
+//! Files are looked up in the first of these directories that is set:
+//!
+//! 1. the directory given to `set_data_dir`, such as by a `--data-dir` flag,
+//! 2. the directory in the `CRYPTOPALS_DATA_DIR` environment variable,
+//! 3. the crate's own `data` directory, fixed at compile time,
+//!
+//! so nothing depends on the current working directory.
+use conversions::{Base64Value, HexValue};
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+/// Environment variable that overrides the data directory.
+pub const DATA_DIR_VAR: &str = "CRYPTOPALS_DATA_DIR";
+
+lazy_static! {
+    static ref DATA_DIR_OVERRIDE: RwLock<Option<PathBuf>> = RwLock::new(None);
+}
+
+/// Use `dir` as the data directory for the rest of the process, ahead of the
+/// environment variable.
+pub fn set_data_dir<P: Into<PathBuf>>(dir: P) {
+    *DATA_DIR_OVERRIDE.write().unwrap() = Some(dir.into());
+}
+
+/// Return the directory data files are loaded from.
+pub fn data_dir() -> PathBuf {
+    if let Some(ref dir) = *DATA_DIR_OVERRIDE.read().unwrap() {
+        return dir.clone();
+    }
+    match env::var_os(DATA_DIR_VAR) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/data")),
+    }
+}
+
+/// Return the path of the data file `name`.
+pub fn path(name: &str) -> PathBuf {
+    data_dir().join(name)
+}
+
+/// Read the data file `name` as text
+///
+/// # Errors
+/// Returns the I/O error, with the path of the file in its message, if the
+/// file can't be read.
+pub fn load_string(name: &str) -> io::Result<String> {
+    let path = path(name);
+    fs::read_to_string(&path)
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))
+}
+
+/// Read the data file `name` as one hex string per line.
+///
+/// # Errors
+/// Returns an error if the file can't be read or isn't hex.
+pub fn load_hex_lines(name: &str) -> io::Result<Vec<Vec<u8>>> {
+    parse_hex_lines(&load_string(name)?).map_err(|e| invalid_data(name, e))
+}
+
+/// Read the data file `name` as wrapped base64.
+///
+/// # Errors
+/// Returns an error if the file can't be read or isn't base64.
+pub fn load_base64(name: &str) -> io::Result<Vec<u8>> {
+    parse_base64(&load_string(name)?).map_err(|e| invalid_data(name, e))
+}
+
+fn invalid_data(name: &str, e: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", path(name).display(), e),
+    )
+}
+
+/// Parse one hex string per line, skipping blank lines
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::data::parse_hex_lines;
+///
+/// assert_eq!(parse_hex_lines("0102\n\nff\n"), Ok(vec![vec![1, 2], vec![255]]));
+/// ```
+pub fn parse_hex_lines(contents: &str) -> Result<Vec<Vec<u8>>, &'static str> {
+    contents
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| HexValue::from_str(line).map(|hex| hex.bytes))
+        .collect()
+}
+
+/// Parse base64 wrapped over several lines
+pub fn parse_base64(contents: &str) -> Result<Vec<u8>, &'static str> {
+    Base64Value::from_wrapped_str(contents).map(|b64| b64.as_bytes().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use data::*;
+
+    #[test]
+    fn test_parse_hex_lines() {
+        assert_eq!(parse_hex_lines(""), Ok(vec![]));
+        assert_eq!(
+            parse_hex_lines("00ff\r\n  0a0b  \n"),
+            Ok(vec![vec![0, 255], vec![10, 11]])
+        );
+        assert!(parse_hex_lines("00ff\nzz\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_base64() {
+        assert_eq!(
+            parse_base64("SGVs\nbG8g\nd29y\nbGQ=\n"),
+            Ok(b"Hello world".to_vec())
+        );
+        assert!(parse_base64("SGVs\nb*8=").is_err());
+    }
+
+    #[test]
+    fn test_load_hex_lines() {
+        let lines = load_hex_lines("4.txt").unwrap();
+        assert_eq!(lines.len(), 327);
+
+        let e = load_hex_lines("missing.txt").unwrap_err();
+        assert_eq!(e.kind(), io::ErrorKind::NotFound);
+        assert!(e.to_string().contains("missing.txt"));
+    }
+}
@@ -0,0 +1,360 @@
+//! AES (FIPS-197) with 128-, 192- and 256-bit keys
+//!
+//! A straightforward byte-oriented implementation that follows the standard's
+//! pseudocode. It uses table lookups and is not constant-time, so it must not
+//! be used to protect real data.
+
+use encrypt::BlockCipher;
+use std::convert::TryFrom;
+
+/// AES block size in bytes
+pub const AES_BLOCK_SIZE: usize = 16;
+
+/// The S-box, FIPS-197 figure 7
+const SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+/// The inverse S-box, FIPS-197 figure 14
+const INV_SBOX: [u8; 256] = [
+    0x52, 0x09, 0x6a, 0xd5, 0x30, 0x36, 0xa5, 0x38, 0xbf, 0x40, 0xa3, 0x9e, 0x81, 0xf3, 0xd7, 0xfb,
+    0x7c, 0xe3, 0x39, 0x82, 0x9b, 0x2f, 0xff, 0x87, 0x34, 0x8e, 0x43, 0x44, 0xc4, 0xde, 0xe9, 0xcb,
+    0x54, 0x7b, 0x94, 0x32, 0xa6, 0xc2, 0x23, 0x3d, 0xee, 0x4c, 0x95, 0x0b, 0x42, 0xfa, 0xc3, 0x4e,
+    0x08, 0x2e, 0xa1, 0x66, 0x28, 0xd9, 0x24, 0xb2, 0x76, 0x5b, 0xa2, 0x49, 0x6d, 0x8b, 0xd1, 0x25,
+    0x72, 0xf8, 0xf6, 0x64, 0x86, 0x68, 0x98, 0x16, 0xd4, 0xa4, 0x5c, 0xcc, 0x5d, 0x65, 0xb6, 0x92,
+    0x6c, 0x70, 0x48, 0x50, 0xfd, 0xed, 0xb9, 0xda, 0x5e, 0x15, 0x46, 0x57, 0xa7, 0x8d, 0x9d, 0x84,
+    0x90, 0xd8, 0xab, 0x00, 0x8c, 0xbc, 0xd3, 0x0a, 0xf7, 0xe4, 0x58, 0x05, 0xb8, 0xb3, 0x45, 0x06,
+    0xd0, 0x2c, 0x1e, 0x8f, 0xca, 0x3f, 0x0f, 0x02, 0xc1, 0xaf, 0xbd, 0x03, 0x01, 0x13, 0x8a, 0x6b,
+    0x3a, 0x91, 0x11, 0x41, 0x4f, 0x67, 0xdc, 0xea, 0x97, 0xf2, 0xcf, 0xce, 0xf0, 0xb4, 0xe6, 0x73,
+    0x96, 0xac, 0x74, 0x22, 0xe7, 0xad, 0x35, 0x85, 0xe2, 0xf9, 0x37, 0xe8, 0x1c, 0x75, 0xdf, 0x6e,
+    0x47, 0xf1, 0x1a, 0x71, 0x1d, 0x29, 0xc5, 0x89, 0x6f, 0xb7, 0x62, 0x0e, 0xaa, 0x18, 0xbe, 0x1b,
+    0xfc, 0x56, 0x3e, 0x4b, 0xc6, 0xd2, 0x79, 0x20, 0x9a, 0xdb, 0xc0, 0xfe, 0x78, 0xcd, 0x5a, 0xf4,
+    0x1f, 0xdd, 0xa8, 0x33, 0x88, 0x07, 0xc7, 0x31, 0xb1, 0x12, 0x10, 0x59, 0x27, 0x80, 0xec, 0x5f,
+    0x60, 0x51, 0x7f, 0xa9, 0x19, 0xb5, 0x4a, 0x0d, 0x2d, 0xe5, 0x7a, 0x9f, 0x93, 0xc9, 0x9c, 0xef,
+    0xa0, 0xe0, 0x3b, 0x4d, 0xae, 0x2a, 0xf5, 0xb0, 0xc8, 0xeb, 0xbb, 0x3c, 0x83, 0x53, 0x99, 0x61,
+    0x17, 0x2b, 0x04, 0x7e, 0xba, 0x77, 0xd6, 0x26, 0xe1, 0x69, 0x14, 0x63, 0x55, 0x21, 0x0c, 0x7d,
+];
+
+/// Round constants for the key expansion, `x^(i-1)` in GF(2^8)
+const RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+/// The AES block cipher with an expanded key
+///
+/// Blocks are 16 bytes in the standard's order: byte `r + 4c` of a block is
+/// row `r`, column `c` of the state.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::conversions::HexValue;
+/// use cryptopals::encrypt::{Aes, BlockCipher};
+///
+/// let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+/// let mut block = *b"sixteen byte msg";
+///
+/// aes.encrypt_block(&mut block);
+/// assert_eq!(HexValue::from_bytes(&block).to_string(), "35689a46dcbd819c80d5a1d74797ef93");
+/// aes.decrypt_block(&mut block);
+/// assert_eq!(&block, b"sixteen byte msg");
+/// ```
+#[derive(Clone)]
+pub struct Aes {
+    round_keys: Vec<[u8; AES_BLOCK_SIZE]>,
+}
+
+impl Aes {
+    /// Expand `key` into the round keys.
+    ///
+    /// # Errors
+    /// Returns an error unless `key` is 16, 24 or 32 bytes long.
+    pub fn new(key: &[u8]) -> Result<Aes, &'static str> {
+        match key.len() {
+            16 | 24 | 32 => {}
+            _ => return Err("key must be 16, 24 or 32 bytes long"),
+        }
+        let key_words = key.len() / 4;
+        let rounds = key_words + 6;
+
+        let mut words: Vec<[u8; 4]> = key.chunks(4).map(|w| [w[0], w[1], w[2], w[3]]).collect();
+        for i in key_words..4 * (rounds + 1) {
+            let mut temp = words[i - 1];
+            if i % key_words == 0 {
+                temp = [temp[1], temp[2], temp[3], temp[0]];
+                sub_word(&mut temp);
+                temp[0] ^= RCON[i / key_words - 1];
+            } else if key_words > 6 && i % key_words == 4 {
+                sub_word(&mut temp);
+            }
+            let previous = words[i - key_words];
+            words.push([
+                previous[0] ^ temp[0],
+                previous[1] ^ temp[1],
+                previous[2] ^ temp[2],
+                previous[3] ^ temp[3],
+            ]);
+        }
+
+        let round_keys = words
+            .chunks(4)
+            .map(|w| {
+                let mut round_key = [0u8; AES_BLOCK_SIZE];
+                for (c, word) in w.iter().enumerate() {
+                    round_key[4 * c..4 * c + 4].copy_from_slice(word);
+                }
+                round_key
+            })
+            .collect();
+        Ok(Aes { round_keys })
+    }
+
+    /// Number of rounds: 10, 12 or 14 depending on the key size.
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len() - 1
+    }
+
+    fn encrypt_state(&self, block: &mut [u8; AES_BLOCK_SIZE]) {
+        let rounds = self.rounds();
+        add_round_key(block, &self.round_keys[0]);
+        for round in 1..rounds {
+            sub_bytes(block, &SBOX);
+            shift_rows(block);
+            mix_columns(block);
+            add_round_key(block, &self.round_keys[round]);
+        }
+        sub_bytes(block, &SBOX);
+        shift_rows(block);
+        add_round_key(block, &self.round_keys[rounds]);
+    }
+
+    fn decrypt_state(&self, block: &mut [u8; AES_BLOCK_SIZE]) {
+        let rounds = self.rounds();
+        add_round_key(block, &self.round_keys[rounds]);
+        for round in (1..rounds).rev() {
+            inv_shift_rows(block);
+            sub_bytes(block, &INV_SBOX);
+            add_round_key(block, &self.round_keys[round]);
+            inv_mix_columns(block);
+        }
+        inv_shift_rows(block);
+        sub_bytes(block, &INV_SBOX);
+        add_round_key(block, &self.round_keys[0]);
+    }
+}
+
+impl BlockCipher for Aes {
+    fn block_size(&self) -> usize {
+        AES_BLOCK_SIZE
+    }
+
+    fn encrypt_block(&self, block: &mut [u8]) {
+        self.encrypt_state(
+            <&mut [u8; AES_BLOCK_SIZE]>::try_from(block).expect("AES blocks are 16 bytes"),
+        );
+    }
+
+    fn decrypt_block(&self, block: &mut [u8]) {
+        self.decrypt_state(
+            <&mut [u8; AES_BLOCK_SIZE]>::try_from(block).expect("AES blocks are 16 bytes"),
+        );
+    }
+}
+
+fn sub_word(word: &mut [u8; 4]) {
+    for b in word.iter_mut() {
+        *b = SBOX[*b as usize];
+    }
+}
+
+fn sub_bytes(state: &mut [u8; AES_BLOCK_SIZE], table: &[u8; 256]) {
+    for b in state.iter_mut() {
+        *b = table[*b as usize];
+    }
+}
+
+fn add_round_key(state: &mut [u8; AES_BLOCK_SIZE], round_key: &[u8; AES_BLOCK_SIZE]) {
+    for (b, k) in state.iter_mut().zip(round_key.iter()) {
+        *b ^= k;
+    }
+}
+
+/// Rotate row `r` of the state left by `r` positions.
+fn shift_rows(state: &mut [u8; AES_BLOCK_SIZE]) {
+    let old = *state;
+    for r in 1..4 {
+        for c in 0..4 {
+            state[r + 4 * c] = old[r + 4 * ((c + r) % 4)];
+        }
+    }
+}
+
+fn inv_shift_rows(state: &mut [u8; AES_BLOCK_SIZE]) {
+    let old = *state;
+    for r in 1..4 {
+        for c in 0..4 {
+            state[r + 4 * ((c + r) % 4)] = old[r + 4 * c];
+        }
+    }
+}
+
+/// Multiply by `x` in GF(2^8) modulo the AES polynomial.
+fn xtime(b: u8) -> u8 {
+    match b & 0x80 {
+        0 => b << 1,
+        _ => (b << 1) ^ 0x1b,
+    }
+}
+
+/// Multiply two elements of GF(2^8).
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        a = xtime(a);
+        b >>= 1;
+    }
+    product
+}
+
+fn mix_columns(state: &mut [u8; AES_BLOCK_SIZE]) {
+    for column in state.chunks_mut(4) {
+        let [a0, a1, a2, a3] = [column[0], column[1], column[2], column[3]];
+        let all = a0 ^ a1 ^ a2 ^ a3;
+        column[0] ^= all ^ xtime(a0 ^ a1);
+        column[1] ^= all ^ xtime(a1 ^ a2);
+        column[2] ^= all ^ xtime(a2 ^ a3);
+        column[3] ^= all ^ xtime(a3 ^ a0);
+    }
+}
+
+fn inv_mix_columns(state: &mut [u8; AES_BLOCK_SIZE]) {
+    for column in state.chunks_mut(4) {
+        let a = [column[0], column[1], column[2], column[3]];
+        for (r, b) in column.iter_mut().enumerate() {
+            *b = gf_mul(a[r], 0x0e)
+                ^ gf_mul(a[(r + 1) % 4], 0x0b)
+                ^ gf_mul(a[(r + 2) % 4], 0x0d)
+                ^ gf_mul(a[(r + 3) % 4], 0x09);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use conversions::HexValue;
+    use encrypt::aes::*;
+    use encrypt::BlockCipher;
+
+    fn block(hex: &str) -> [u8; AES_BLOCK_SIZE] {
+        let mut block = [0u8; AES_BLOCK_SIZE];
+        block.copy_from_slice(&HexValue::from_str(hex).unwrap().bytes);
+        block
+    }
+
+    fn check(key: &str, plaintext: &str, ciphertext: &str) {
+        let aes = Aes::new(&HexValue::from_str(key).unwrap().bytes).unwrap();
+        let mut data = block(plaintext);
+        aes.encrypt_block(&mut data);
+        assert_eq!(data, block(ciphertext));
+        aes.decrypt_block(&mut data);
+        assert_eq!(data, block(plaintext));
+    }
+
+    #[test]
+    fn test_aes_fips197_appendix_b() {
+        check(
+            "2b7e151628aed2a6abf7158809cf4f3c",
+            "3243f6a8885a308d313198a2e0370734",
+            "3925841d02dc09fbdc118597196a0b32",
+        );
+    }
+
+    #[test]
+    fn test_aes_fips197_appendix_c() {
+        let plaintext = "00112233445566778899aabbccddeeff";
+        check(
+            "000102030405060708090a0b0c0d0e0f",
+            plaintext,
+            "69c4e0d86a7b0430d8cdb78070b4c55a",
+        );
+        check(
+            "000102030405060708090a0b0c0d0e0f1011121314151617",
+            plaintext,
+            "dda97ca4864cdfe06eaf70a0ec0d7191",
+        );
+        check(
+            "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f",
+            plaintext,
+            "8ea2b7ca516745bfeafc49904b496089",
+        );
+    }
+
+    #[test]
+    fn test_aes_key_expansion() {
+        // FIPS-197 appendix A.1: w[40..44] and A.3: w[56..60]
+        let aes = Aes::new(
+            &HexValue::from_str("2b7e151628aed2a6abf7158809cf4f3c")
+                .unwrap()
+                .bytes,
+        )
+        .unwrap();
+        assert_eq!(aes.rounds(), 10);
+        assert_eq!(
+            aes.round_keys[10],
+            block("d014f9a8c9ee2589e13f0cc8b6630ca6")
+        );
+
+        let aes = Aes::new(
+            &HexValue::from_str("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4")
+                .unwrap()
+                .bytes,
+        )
+        .unwrap();
+        assert_eq!(aes.rounds(), 14);
+        assert_eq!(
+            aes.round_keys[14],
+            block("fe4890d1e6188d0b046df344706c631e")
+        );
+    }
+
+    #[test]
+    fn test_aes_invalid_key() {
+        assert!(Aes::new(b"").is_err());
+        assert!(Aes::new(b"fifteen bytes!!").is_err());
+        assert!(Aes::new(&[0u8; 33]).is_err());
+        assert_eq!(Aes::new(&[0u8; 24]).unwrap().rounds(), 12);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_aes_short_block() {
+        Aes::new(b"YELLOW SUBMARINE")
+            .unwrap()
+            .encrypt_block(&mut [0u8; 8]);
+    }
+
+    #[test]
+    fn test_aes_gf_mul() {
+        // FIPS-197 section 4.2: {57} * {83} = {c1} and {57} * {13} = {fe}
+        assert_eq!(gf_mul(0x57, 0x83), 0xc1);
+        assert_eq!(gf_mul(0x57, 0x13), 0xfe);
+        assert_eq!(xtime(0x8e), 0x07);
+    }
+}
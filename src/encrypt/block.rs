@@ -0,0 +1,90 @@
+use encrypt::PaddingError;
+use std::error;
+use std::fmt;
+
+/// A cipher that encrypts fixed-size blocks
+///
+/// The modes of operation in `encrypt` are generic over this trait, so a
+/// cipher only has to implement it to be usable with all of them.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{Aes, BlockCipher};
+///
+/// let cipher: Box<dyn BlockCipher> = Box::new(Aes::new(b"YELLOW SUBMARINE").unwrap());
+/// let mut block = [0u8; 16];
+///
+/// assert_eq!(cipher.block_size(), 16);
+/// cipher.encrypt_block(&mut block);
+/// cipher.decrypt_block(&mut block);
+/// assert_eq!(block, [0u8; 16]);
+/// ```
+pub trait BlockCipher {
+    /// Size of a block in bytes, which the modes require to be nonzero
+    fn block_size(&self) -> usize;
+
+    /// Encrypt a single block in place.
+    ///
+    /// # Panics
+    /// Implementations panic if `block` is not exactly `block_size()` bytes.
+    fn encrypt_block(&self, block: &mut [u8]);
+
+    /// Decrypt a single block in place.
+    ///
+    /// # Panics
+    /// Implementations panic if `block` is not exactly `block_size()` bytes.
+    fn decrypt_block(&self, block: &mut [u8]);
+}
+
+/// Why a mode of operation could not encrypt or decrypt
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeError {
+    /// The IV is not one block long.
+    InvalidIv,
+    /// The CTR counter layout does not fit the block size.
+    InvalidCounterLayout,
+    /// The data length is not a multiple of the block size.
+    Unaligned,
+    /// The cipher reports a block size of zero.
+    ZeroBlockSize,
+    /// The block size is larger than PKCS#7 padding can encode.
+    BlockSizeTooLarge,
+    /// The data decrypted, but its padding is invalid.
+    Padding(PaddingError),
+}
+
+impl ModeError {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            ModeError::InvalidIv => "IV must be one block long",
+            ModeError::InvalidCounterLayout => "counter layout does not fit the block size",
+            ModeError::Unaligned => "data length is not a multiple of the block size",
+            ModeError::ZeroBlockSize => "cipher has a block size of zero",
+            ModeError::BlockSizeTooLarge => "block size is too large for PKCS#7 padding",
+            ModeError::Padding(e) => e.as_str(),
+        }
+    }
+}
+
+impl From<PaddingError> for ModeError {
+    fn from(e: PaddingError) -> ModeError {
+        ModeError::Padding(e)
+    }
+}
+
+impl fmt::Display for ModeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl error::Error for ModeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            ModeError::Padding(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
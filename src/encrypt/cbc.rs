@@ -0,0 +1,307 @@
+use conversions::HexValue;
+use encrypt::ecb::{check_block_aligned, pad_to_block};
+use encrypt::{pkcs7_unpad, BlockCipher, ModeError};
+
+/// Encrypt `data` in CBC mode after padding it with PKCS#7
+///
+/// Each plaintext block is XORed with the previous ciphertext block, or with
+/// `iv` for the first block, before it is encrypted.
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long or if the block size is zero
+/// or greater than 255, which PKCS#7 can't pad to.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{cbc_decrypt, cbc_encrypt, Aes};
+///
+/// let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+/// let iv = [0u8; 16];
+/// let ciphertext = cbc_encrypt(&aes, &iv, b"attack at dawn, attack at dawn").unwrap();
+///
+/// assert_eq!(ciphertext.len(), 32);
+/// assert_eq!(cbc_decrypt(&aes, &iv, &ciphertext).unwrap(), b"attack at dawn, attack at dawn");
+/// ```
+pub fn cbc_encrypt<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    cbc_encrypt_raw(cipher, iv, &pad_to_block(data, cipher.block_size())?)
+}
+
+/// Decrypt `data` in CBC mode and remove the PKCS#7 padding
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long, if the length of `data` is
+/// not a nonzero multiple of the block size or if the decrypted padding is
+/// invalid. Invalid padding is reported as `ModeError::Padding`, so a padding
+/// oracle can tell it apart from the other errors.
+pub fn cbc_decrypt<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    let plaintext = cbc_decrypt_raw(cipher, iv, data)?;
+    Ok(pkcs7_unpad(&plaintext, cipher.block_size())?.to_vec())
+}
+
+/// Encrypt `data` in CBC mode without padding
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long or if the length of `data`
+/// is not a multiple of the block size.
+pub fn cbc_encrypt_raw<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    check_iv(iv, cipher.block_size())?;
+    check_block_aligned(data, cipher.block_size())?;
+
+    let mut out = Vec::with_capacity(data.len());
+    let mut previous = HexValue::from_bytes(iv);
+    for block in data.chunks(cipher.block_size()) {
+        let mut block = HexValue::from_bytes(block).xor(&previous);
+        cipher.encrypt_block(&mut block.bytes);
+        out.extend_from_slice(&block.bytes);
+        previous = block;
+    }
+    Ok(out)
+}
+
+/// Decrypt `data` in CBC mode without removing padding
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long or if the length of `data`
+/// is not a multiple of the block size.
+pub fn cbc_decrypt_raw<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    check_iv(iv, cipher.block_size())?;
+    check_block_aligned(data, cipher.block_size())?;
+
+    let mut out = Vec::with_capacity(data.len());
+    let mut previous = HexValue::from_bytes(iv);
+    for block in data.chunks(cipher.block_size()) {
+        let mut decrypted = HexValue::from_bytes(block);
+        cipher.decrypt_block(&mut decrypted.bytes);
+        out.extend_from_slice(&decrypted.xor(&previous).bytes);
+        previous = HexValue::from_bytes(block);
+    }
+    Ok(out)
+}
+
+/// Encrypt `data` in PCBC mode after padding it with PKCS#7
+///
+/// PCBC is CBC where each plaintext block is also XORed into the chaining
+/// value, so a change to any ciphertext block garbles every plaintext block
+/// after it.
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long or if the block size is zero
+/// or greater than 255, which PKCS#7 can't pad to.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{pcbc_decrypt, pcbc_encrypt, Aes};
+///
+/// let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+/// let iv = [0u8; 16];
+/// let ciphertext = pcbc_encrypt(&aes, &iv, b"attack at dawn, attack at dawn").unwrap();
+///
+/// assert_eq!(pcbc_decrypt(&aes, &iv, &ciphertext).unwrap(), b"attack at dawn, attack at dawn");
+/// ```
+pub fn pcbc_encrypt<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    pcbc_encrypt_raw(cipher, iv, &pad_to_block(data, cipher.block_size())?)
+}
+
+/// Decrypt `data` in PCBC mode and remove the PKCS#7 padding
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long, if the length of `data` is
+/// not a nonzero multiple of the block size or if the decrypted padding is
+/// invalid.
+pub fn pcbc_decrypt<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    let plaintext = pcbc_decrypt_raw(cipher, iv, data)?;
+    Ok(pkcs7_unpad(&plaintext, cipher.block_size())?.to_vec())
+}
+
+/// Encrypt `data` in PCBC mode without padding
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long or if the length of `data`
+/// is not a multiple of the block size.
+pub fn pcbc_encrypt_raw<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    check_iv(iv, cipher.block_size())?;
+    check_block_aligned(data, cipher.block_size())?;
+
+    let mut out = Vec::with_capacity(data.len());
+    let mut previous = HexValue::from_bytes(iv);
+    for block in data.chunks(cipher.block_size()) {
+        let plaintext = HexValue::from_bytes(block);
+        let mut ciphertext = plaintext.xor(&previous);
+        cipher.encrypt_block(&mut ciphertext.bytes);
+        out.extend_from_slice(&ciphertext.bytes);
+        previous = plaintext.xor(&ciphertext);
+    }
+    Ok(out)
+}
+
+/// Decrypt `data` in PCBC mode without removing padding
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long or if the length of `data`
+/// is not a multiple of the block size.
+pub fn pcbc_decrypt_raw<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    check_iv(iv, cipher.block_size())?;
+    check_block_aligned(data, cipher.block_size())?;
+
+    let mut out = Vec::with_capacity(data.len());
+    let mut previous = HexValue::from_bytes(iv);
+    for block in data.chunks(cipher.block_size()) {
+        let ciphertext = HexValue::from_bytes(block);
+        let mut plaintext = HexValue::from_bytes(block);
+        cipher.decrypt_block(&mut plaintext.bytes);
+        plaintext = plaintext.xor(&previous);
+        out.extend_from_slice(&plaintext.bytes);
+        previous = plaintext.xor(&ciphertext);
+    }
+    Ok(out)
+}
+
+pub(crate) fn check_iv(iv: &[u8], block_size: usize) -> Result<(), ModeError> {
+    if block_size == 0 {
+        return Err(ModeError::ZeroBlockSize);
+    }
+    match iv.len() == block_size {
+        true => Ok(()),
+        false => Err(ModeError::InvalidIv),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use encrypt::cbc::*;
+    use encrypt::{ecb_encrypt_raw, Aes, PaddingError};
+
+    #[test]
+    fn test_cbc_round_trip() {
+        let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+        let iv: Vec<u8> = (0..16).collect();
+        for len in 0..50 {
+            let data: Vec<u8> = (0..len).collect();
+            let ciphertext = cbc_encrypt(&aes, &iv, &data).unwrap();
+            assert_eq!(ciphertext.len(), (len as usize / 16 + 1) * 16);
+            assert_eq!(cbc_decrypt(&aes, &iv, &ciphertext).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_cbc_chains_blocks() {
+        // With a zero IV the first block matches ECB, but equal plaintext
+        // blocks after it do not give equal ciphertext blocks.
+        let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+        let data = [b'A'; 32];
+        let ciphertext = cbc_encrypt_raw(&aes, &[0; 16], &data).unwrap();
+        assert_eq!(
+            &ciphertext[..16],
+            &ecb_encrypt_raw(&aes, &data[..16]).unwrap()[..]
+        );
+        assert_ne!(&ciphertext[..16], &ciphertext[16..]);
+    }
+
+    #[test]
+    fn test_cbc_errors() {
+        let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+        assert_eq!(
+            cbc_encrypt(&aes, &[0; 15], b"data"),
+            Err(ModeError::InvalidIv)
+        );
+        assert_eq!(
+            cbc_decrypt(&aes, &[0; 17], &[0; 16]),
+            Err(ModeError::InvalidIv)
+        );
+        assert_eq!(
+            cbc_encrypt_raw(&aes, &[0; 16], b"data"),
+            Err(ModeError::Unaligned)
+        );
+        assert_eq!(
+            cbc_decrypt_raw(&aes, &[0; 16], &[0; 20]),
+            Err(ModeError::Unaligned)
+        );
+        assert_eq!(
+            cbc_decrypt(&aes, &[0; 16], b""),
+            Err(ModeError::Padding(PaddingError::InvalidLength))
+        );
+
+        // Flipping a bit of the IV flips the same bit of the first plaintext
+        // block, which holds the padding of a one-block message.
+        let ciphertext = cbc_encrypt(&aes, &[0; 16], b"data").unwrap();
+        let mut iv = [0u8; 16];
+        iv[15] ^= 0x10;
+        assert!(matches!(
+            cbc_decrypt(&aes, &iv, &ciphertext),
+            Err(ModeError::Padding(_))
+        ));
+    }
+
+    #[test]
+    fn test_pcbc() {
+        let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+        let iv: Vec<u8> = (0..16).collect();
+        for len in 0..50 {
+            let data: Vec<u8> = (0..len).collect();
+            let ciphertext = pcbc_encrypt(&aes, &iv, &data).unwrap();
+            assert_eq!(pcbc_decrypt(&aes, &iv, &ciphertext).unwrap(), data);
+        }
+
+        // The first block matches CBC; a flipped bit garbles its own block and
+        // every block after it, where CBC would garble only the next one.
+        let data = [b'A'; 64];
+        let mut ciphertext = pcbc_encrypt_raw(&aes, &iv, &data).unwrap();
+        assert_eq!(
+            &ciphertext[..16],
+            &cbc_encrypt_raw(&aes, &iv, &data).unwrap()[..16]
+        );
+        ciphertext[20] ^= 1;
+        let plaintext = pcbc_decrypt_raw(&aes, &iv, &ciphertext).unwrap();
+        assert_eq!(&plaintext[..16], &data[..16]);
+        for block in 1..4 {
+            let range = block * 16..(block + 1) * 16;
+            assert_ne!(&plaintext[range.clone()], &data[range]);
+        }
+
+        assert_eq!(
+            pcbc_encrypt(&aes, &[0; 8], b"data"),
+            Err(ModeError::InvalidIv)
+        );
+        assert_eq!(
+            pcbc_encrypt_raw(&aes, &iv, b"data"),
+            Err(ModeError::Unaligned)
+        );
+        assert_eq!(pcbc_decrypt(&aes, &iv, &[0; 15]), Err(ModeError::Unaligned));
+    }
+}
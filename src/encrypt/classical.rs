@@ -0,0 +1,246 @@
+//! Classical letter ciphers. Only ASCII letters are enciphered; their case is
+//! kept and every other character passes through unchanged.
+
+/// Shift each letter of `text` through the alphabet with `shift_letter`,
+/// passing the letter's index among the letters of `text` and its position in
+/// the alphabet.
+fn map_letters<F: FnMut(usize, u8) -> u8>(text: &str, mut shift_letter: F) -> String {
+    let mut letter_index = 0;
+    text.chars()
+        .map(|c| {
+            if !c.is_ascii_alphabetic() {
+                return c;
+            }
+            let base = if c.is_ascii_uppercase() { b'A' } else { b'a' };
+            let shifted = shift_letter(letter_index, c as u8 - base) % 26;
+            letter_index += 1;
+            (base + shifted) as char
+        })
+        .collect()
+}
+
+/// Encrypt `text` with the Caesar cipher, shifting each letter `shift` places
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::caesar_encrypt;
+///
+/// assert_eq!(caesar_encrypt("Attack at dawn!", 3), "Dwwdfn dw gdzq!");
+/// ```
+pub fn caesar_encrypt(text: &str, shift: u8) -> String {
+    map_letters(text, |_, p| p + shift % 26)
+}
+
+pub fn caesar_decrypt(text: &str, shift: u8) -> String {
+    caesar_encrypt(text, 26 - shift % 26)
+}
+
+/// Convert a Vigenère key to its shifts, rejecting empty keys and keys with
+/// anything but ASCII letters.
+fn vigenere_shifts(key: &str) -> Result<Vec<u8>, &'static str> {
+    if key.is_empty() {
+        return Err("key is empty");
+    }
+    if !key.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Err("key contains characters that are not letters");
+    }
+    Ok(key.bytes().map(|b| b.to_ascii_lowercase() - b'a').collect())
+}
+
+/// Encrypt `text` with the Vigenère cipher
+///
+/// The key advances only on letters, and its case is ignored.
+///
+/// # Errors
+/// Returns an error if `key` is empty or has characters that are not letters.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::vigenere_encrypt;
+///
+/// assert_eq!(vigenere_encrypt("ATTACK AT DAWN", "lemon"), Ok(String::from("LXFOPV EF RNHR")));
+/// ```
+pub fn vigenere_encrypt(text: &str, key: &str) -> Result<String, &'static str> {
+    let shifts = vigenere_shifts(key)?;
+    Ok(map_letters(text, |i, p| p + shifts[i % shifts.len()]))
+}
+
+pub fn vigenere_decrypt(text: &str, key: &str) -> Result<String, &'static str> {
+    let shifts = vigenere_shifts(key)?;
+    Ok(map_letters(text, |i, c| c + 26 - shifts[i % shifts.len()]))
+}
+
+/// Return the inverse of `a` modulo 26, if it has one.
+pub fn mod_inverse_26(a: u8) -> Option<u8> {
+    (1..26).find(|&x| (u32::from(a) * u32::from(x)) % 26 == 1)
+}
+
+/// Encrypt `text` with the affine cipher, mapping letter `p` to `a * p + b`
+///
+/// # Errors
+/// Returns an error if `a` is not coprime with 26, which would make the
+/// cipher impossible to decrypt.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::affine_encrypt;
+///
+/// assert_eq!(affine_encrypt("affine cipher", 5, 8), Ok(String::from("ihhwvc swfrcp")));
+/// ```
+pub fn affine_encrypt(text: &str, a: u8, b: u8) -> Result<String, &'static str> {
+    if mod_inverse_26(a % 26).is_none() {
+        return Err("multiplier is not coprime with 26");
+    }
+    let (a, b) = (u32::from(a % 26), u32::from(b % 26));
+    Ok(map_letters(text, |_, p| {
+        ((a * u32::from(p) + b) % 26) as u8
+    }))
+}
+
+pub fn affine_decrypt(text: &str, a: u8, b: u8) -> Result<String, &'static str> {
+    let a_inv = u32::from(mod_inverse_26(a % 26).ok_or("multiplier is not coprime with 26")?);
+    let b = u32::from(b % 26);
+    Ok(map_letters(text, |_, c| {
+        ((a_inv * (u32::from(c) + 26 - b)) % 26) as u8
+    }))
+}
+
+/// The key of a monoalphabetic substitution cipher: the ciphertext letter for
+/// each plaintext letter
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SubstitutionKey {
+    forward: [u8; 26],
+    inverse: [u8; 26],
+}
+
+impl SubstitutionKey {
+    /// Create a key from a permutation of the alphabet, where the `i`th letter
+    /// is the encryption of the `i`th letter of the alphabet. Case is ignored.
+    ///
+    /// # Errors
+    /// Returns an error unless `alphabet` holds each of the 26 letters once.
+    pub fn from_alphabet(alphabet: &str) -> Result<SubstitutionKey, &'static str> {
+        if alphabet.len() != 26 || !alphabet.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err("key must be a permutation of the 26 letters");
+        }
+        let mut forward = [0; 26];
+        for (i, b) in alphabet.bytes().enumerate() {
+            forward[i] = b.to_ascii_lowercase() - b'a';
+        }
+        SubstitutionKey::from_permutation(forward)
+    }
+
+    /// Create a key from the ciphertext letter index of each plaintext letter
+    /// index.
+    ///
+    /// # Errors
+    /// Returns an error unless `forward` is a permutation of `0..26`.
+    pub fn from_permutation(forward: [u8; 26]) -> Result<SubstitutionKey, &'static str> {
+        let mut inverse = [26; 26];
+        for (p, &c) in forward.iter().enumerate() {
+            match inverse.get(c as usize) {
+                Some(&26) => inverse[c as usize] = p as u8,
+                _ => return Err("key must be a permutation of the 26 letters"),
+            }
+        }
+        Ok(SubstitutionKey { forward, inverse })
+    }
+
+    /// The ciphertext alphabet, in lowercase.
+    pub fn alphabet(&self) -> String {
+        self.forward.iter().map(|&c| (b'a' + c) as char).collect()
+    }
+
+    pub fn permutation(&self) -> [u8; 26] {
+        self.forward
+    }
+}
+
+/// Encrypt `text` with a monoalphabetic substitution cipher
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{substitution_encrypt, SubstitutionKey};
+///
+/// let key = SubstitutionKey::from_alphabet("QWERTYUIOPASDFGHJKLZXCVBNM").unwrap();
+///
+/// assert_eq!(substitution_encrypt("Hello, world", &key), "Itssg, vgksr");
+/// ```
+pub fn substitution_encrypt(text: &str, key: &SubstitutionKey) -> String {
+    map_letters(text, |_, p| key.forward[p as usize])
+}
+
+pub fn substitution_decrypt(text: &str, key: &SubstitutionKey) -> String {
+    map_letters(text, |_, c| key.inverse[c as usize])
+}
+
+#[cfg(test)]
+mod tests {
+    use encrypt::classical::*;
+
+    #[test]
+    fn test_caesar() {
+        assert_eq!(caesar_encrypt("xyz XYZ", 3), "abc ABC");
+        assert_eq!(caesar_encrypt("abc", 29), "def");
+        assert_eq!(caesar_decrypt("abc ABC", 3), "xyz XYZ");
+        assert_eq!(
+            caesar_decrypt(&caesar_encrypt("Héllo 123", 13), 13),
+            "Héllo 123"
+        );
+    }
+
+    #[test]
+    fn test_vigenere() {
+        let ciphertext = vigenere_encrypt("Attack at dawn", "LEMON").unwrap();
+        assert_eq!(ciphertext, "Lxfopv ef rnhr");
+        assert_eq!(
+            vigenere_decrypt(&ciphertext, "lemon"),
+            Ok(String::from("Attack at dawn"))
+        );
+        assert!(vigenere_encrypt("abc", "").is_err());
+        assert!(vigenere_encrypt("abc", "le mon").is_err());
+        assert!(vigenere_decrypt("abc", "").is_err());
+    }
+
+    #[test]
+    fn test_affine() {
+        assert_eq!(mod_inverse_26(5), Some(21));
+        assert_eq!(mod_inverse_26(13), None);
+
+        let ciphertext = affine_encrypt("Affine Cipher", 5, 8).unwrap();
+        assert_eq!(ciphertext, "Ihhwvc Swfrcp");
+        assert_eq!(
+            affine_decrypt(&ciphertext, 5, 8),
+            Ok(String::from("Affine Cipher"))
+        );
+        assert!(affine_encrypt("abc", 2, 1).is_err());
+        assert!(affine_decrypt("abc", 13, 1).is_err());
+    }
+
+    #[test]
+    fn test_substitution() {
+        let key = SubstitutionKey::from_alphabet("qwertyuiopasdfghjklzxcvbnm").unwrap();
+        assert_eq!(key.alphabet(), "qwertyuiopasdfghjklzxcvbnm");
+        let ciphertext = substitution_encrypt("The Quick Brown Fox!", &key);
+        assert_eq!(ciphertext, "Zit Jxoea Wkgvf Ygb!");
+        assert_eq!(
+            substitution_decrypt(&ciphertext, &key),
+            "The Quick Brown Fox!"
+        );
+
+        assert!(SubstitutionKey::from_alphabet("abc").is_err());
+        assert!(SubstitutionKey::from_alphabet("aacdefghijklmnopqrstuvwxyz").is_err());
+        assert!(SubstitutionKey::from_alphabet("abcdefghijklmnopqrstuvwxy1").is_err());
+        let mut permutation = [0; 26];
+        permutation[0] = 26;
+        assert!(SubstitutionKey::from_permutation(permutation).is_err());
+    }
+}
@@ -0,0 +1,214 @@
+use encrypt::{BlockCipher, ModeError};
+
+/// How the counter blocks of CTR mode are built
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CounterLayout {
+    /// A 64-bit little-endian nonce followed by a 64-bit little-endian block
+    /// count starting at zero, as in Cryptopals challenge 18. Requires a
+    /// 16-byte block.
+    LittleEndian64(u64),
+    /// The whole block is a big-endian counter starting at the given initial
+    /// counter block and wrapping around, as in NIST SP 800-38A.
+    BigEndian(Vec<u8>),
+}
+
+/// CTR mode as a keystream generator
+///
+/// Keystream block `i` is the encryption of counter block `i`, so any part of
+/// the keystream can be computed without the parts before it. Encryption and
+/// decryption are the same operation.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{Aes, CounterLayout, Ctr};
+///
+/// let ctr = Ctr::new(Aes::new(b"YELLOW SUBMARINE").unwrap(), CounterLayout::LittleEndian64(0))
+///     .unwrap();
+/// let mut data = b"attack at dawn, attack at dusk".to_vec();
+///
+/// ctr.apply(0, &mut data);
+/// let mut tail = data[20..].to_vec();
+/// ctr.apply(20, &mut tail);
+/// assert_eq!(tail, b"attack at dusk"[4..]);
+/// ```
+pub struct Ctr<C: BlockCipher> {
+    cipher: C,
+    layout: CounterLayout,
+}
+
+impl<C: BlockCipher> Ctr<C> {
+    /// # Errors
+    /// Returns `ModeError::ZeroBlockSize` if the cipher reports a block size of
+    /// zero and `ModeError::InvalidCounterLayout` if `layout` does not fit the
+    /// block size.
+    pub fn new(cipher: C, layout: CounterLayout) -> Result<Ctr<C>, ModeError> {
+        if cipher.block_size() == 0 {
+            return Err(ModeError::ZeroBlockSize);
+        }
+        let fits = match layout {
+            CounterLayout::LittleEndian64(_) => cipher.block_size() == 16,
+            CounterLayout::BigEndian(ref initial) => initial.len() == cipher.block_size(),
+        };
+        match fits {
+            true => Ok(Ctr { cipher, layout }),
+            false => Err(ModeError::InvalidCounterLayout),
+        }
+    }
+
+    /// The counter block for keystream block `index`.
+    pub fn counter_block(&self, index: u64) -> Vec<u8> {
+        match self.layout {
+            CounterLayout::LittleEndian64(nonce) => {
+                let mut block = nonce.to_le_bytes().to_vec();
+                block.extend_from_slice(&index.to_le_bytes());
+                block
+            }
+            CounterLayout::BigEndian(ref initial) => {
+                let mut block = initial.clone();
+                let mut carry = index;
+                for b in block.iter_mut().rev() {
+                    if carry == 0 {
+                        break;
+                    }
+                    let sum = u64::from(*b) + (carry & 0xff);
+                    *b = sum as u8;
+                    carry = (carry >> 8) + (sum >> 8);
+                }
+                block
+            }
+        }
+    }
+
+    /// The keystream block at `index`.
+    pub fn keystream_block(&self, index: u64) -> Vec<u8> {
+        let mut block = self.counter_block(index);
+        self.cipher.encrypt_block(&mut block);
+        block
+    }
+
+    /// `len` bytes of keystream starting at byte `offset`.
+    pub fn keystream(&self, offset: u64, len: usize) -> Vec<u8> {
+        let mut keystream = vec![0; len];
+        self.apply(offset, &mut keystream);
+        keystream
+    }
+
+    /// XOR `data` in place with the keystream starting at byte `offset`.
+    ///
+    /// To re-encrypt part of a message, apply this to the new plaintext with
+    /// the offset at which it starts in the message.
+    pub fn apply(&self, offset: u64, data: &mut [u8]) {
+        let block_size = self.cipher.block_size() as u64;
+        let mut index = offset / block_size;
+        let mut skip = (offset % block_size) as usize;
+        let mut rest = data;
+        while !rest.is_empty() {
+            let keystream = self.keystream_block(index);
+            let n = rest.len().min(keystream.len() - skip);
+            let (chunk, tail) = rest.split_at_mut(n);
+            for (b, k) in chunk.iter_mut().zip(keystream[skip..].iter()) {
+                *b ^= k;
+            }
+            rest = tail;
+            index = index.wrapping_add(1);
+            skip = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use encrypt::ctr::*;
+    use encrypt::Aes;
+
+    fn aes() -> Aes {
+        Aes::new(b"YELLOW SUBMARINE").unwrap()
+    }
+
+    #[test]
+    fn test_ctr_counter_blocks() {
+        let ctr = Ctr::new(aes(), CounterLayout::LittleEndian64(0x0102)).unwrap();
+        assert_eq!(
+            ctr.counter_block(3),
+            vec![2, 1, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        let mut initial = vec![0; 16];
+        initial[14] = 0xff;
+        initial[15] = 0xfe;
+        let ctr = Ctr::new(aes(), CounterLayout::BigEndian(initial)).unwrap();
+        assert_eq!(&ctr.counter_block(0)[13..], &[0, 0xff, 0xfe]);
+        assert_eq!(&ctr.counter_block(3)[13..], &[1, 0, 1]);
+
+        let ctr = Ctr::new(aes(), CounterLayout::BigEndian(vec![0xff; 16])).unwrap();
+        assert_eq!(ctr.counter_block(1), vec![0; 16]);
+        // 2^128 - 1 + 2^64 - 1 wraps around to 2^64 - 2.
+        let mut expected = vec![0; 8];
+        expected.extend_from_slice(&(u64::MAX - 1).to_be_bytes());
+        assert_eq!(ctr.counter_block(u64::MAX), expected);
+    }
+
+    #[test]
+    fn test_ctr_random_access() {
+        let ctr = Ctr::new(aes(), CounterLayout::LittleEndian64(7)).unwrap();
+        let keystream = ctr.keystream(0, 100);
+        for offset in 0..60 {
+            for len in [0, 1, 15, 16, 17, 40].iter().cloned() {
+                assert_eq!(
+                    ctr.keystream(offset as u64, len),
+                    &keystream[offset..offset + len]
+                );
+            }
+        }
+        assert_eq!(ctr.keystream_block(2), &keystream[32..48]);
+    }
+
+    #[test]
+    fn test_ctr_apply() {
+        let ctr = Ctr::new(aes(), CounterLayout::LittleEndian64(0)).unwrap();
+        let plaintext: Vec<u8> = (0..50).collect();
+        let mut data = plaintext.clone();
+        ctr.apply(0, &mut data);
+        assert_ne!(data, plaintext);
+
+        // Editing part of the message only changes that part.
+        let mut edit = b"EDIT".to_vec();
+        ctr.apply(21, &mut edit);
+        data[21..25].copy_from_slice(&edit);
+        ctr.apply(0, &mut data);
+        assert_eq!(&data[..21], &plaintext[..21]);
+        assert_eq!(&data[21..25], b"EDIT");
+        assert_eq!(&data[25..], &plaintext[25..]);
+    }
+
+    #[test]
+    fn test_ctr_invalid_layout() {
+        assert!(matches!(
+            Ctr::new(aes(), CounterLayout::BigEndian(vec![0; 8])),
+            Err(ModeError::InvalidCounterLayout)
+        ));
+
+        struct NullCipher;
+
+        impl BlockCipher for NullCipher {
+            fn block_size(&self) -> usize {
+                0
+            }
+
+            fn encrypt_block(&self, _: &mut [u8]) {}
+
+            fn decrypt_block(&self, _: &mut [u8]) {}
+        }
+
+        assert!(matches!(
+            Ctr::new(NullCipher, CounterLayout::BigEndian(vec![])),
+            Err(ModeError::ZeroBlockSize)
+        ));
+        assert!(matches!(
+            Ctr::new(NullCipher, CounterLayout::LittleEndian64(0)),
+            Err(ModeError::ZeroBlockSize)
+        ));
+    }
+}
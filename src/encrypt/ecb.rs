@@ -0,0 +1,212 @@
+use encrypt::{pkcs7_pad, pkcs7_unpad, BlockCipher, ModeError};
+
+/// Encrypt `data` in ECB mode after padding it with PKCS#7
+///
+/// ECB encrypts every block independently, so equal plaintext blocks give
+/// equal ciphertext blocks; see `analysis::detect_ecb`.
+///
+/// # Errors
+/// Returns an error if the block size is zero or greater than 255, which
+/// PKCS#7 can't pad to.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{ecb_decrypt, ecb_encrypt, Aes};
+///
+/// let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+/// let ciphertext = ecb_encrypt(&aes, b"attack at dawn").unwrap();
+///
+/// assert_eq!(ciphertext.len(), 16);
+/// assert_eq!(ecb_decrypt(&aes, &ciphertext).unwrap(), b"attack at dawn");
+/// ```
+pub fn ecb_encrypt<C: BlockCipher + ?Sized>(cipher: &C, data: &[u8]) -> Result<Vec<u8>, ModeError> {
+    let mut out = pad_to_block(data, cipher.block_size())?;
+    apply_blocks(&mut out, cipher.block_size(), |block| {
+        cipher.encrypt_block(block)
+    });
+    Ok(out)
+}
+
+/// Decrypt `data` in ECB mode and remove the PKCS#7 padding
+///
+/// # Errors
+/// Returns an error if the length of `data` is not a nonzero multiple of the
+/// block size or if the decrypted padding is invalid.
+pub fn ecb_decrypt<C: BlockCipher + ?Sized>(cipher: &C, data: &[u8]) -> Result<Vec<u8>, ModeError> {
+    let plaintext = ecb_decrypt_raw(cipher, data)?;
+    Ok(pkcs7_unpad(&plaintext, cipher.block_size())?.to_vec())
+}
+
+/// Encrypt `data` in ECB mode without padding
+///
+/// # Errors
+/// Returns an error if the length of `data` is not a multiple of the block
+/// size.
+pub fn ecb_encrypt_raw<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    let mut out = data.to_vec();
+    check_block_aligned(&out, cipher.block_size())?;
+    apply_blocks(&mut out, cipher.block_size(), |block| {
+        cipher.encrypt_block(block)
+    });
+    Ok(out)
+}
+
+/// Decrypt `data` in ECB mode without removing padding
+///
+/// # Errors
+/// Returns an error if the length of `data` is not a multiple of the block
+/// size.
+pub fn ecb_decrypt_raw<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    let mut out = data.to_vec();
+    check_block_aligned(&out, cipher.block_size())?;
+    apply_blocks(&mut out, cipher.block_size(), |block| {
+        cipher.decrypt_block(block)
+    });
+    Ok(out)
+}
+
+pub(crate) fn check_block_aligned(data: &[u8], block_size: usize) -> Result<(), ModeError> {
+    if block_size == 0 {
+        return Err(ModeError::ZeroBlockSize);
+    }
+    match data.len() % block_size {
+        0 => Ok(()),
+        _ => Err(ModeError::Unaligned),
+    }
+}
+
+/// Pad `data` with PKCS#7, checking first that the block size can be padded
+/// to instead of panicking.
+pub(crate) fn pad_to_block(data: &[u8], block_size: usize) -> Result<Vec<u8>, ModeError> {
+    match block_size {
+        0 => Err(ModeError::ZeroBlockSize),
+        1..=255 => Ok(pkcs7_pad(data, block_size)),
+        _ => Err(ModeError::BlockSizeTooLarge),
+    }
+}
+
+fn apply_blocks<F: FnMut(&mut [u8])>(data: &mut [u8], block_size: usize, f: F) {
+    data.chunks_mut(block_size).for_each(f);
+}
+
+#[cfg(test)]
+mod tests {
+    use encrypt::ecb::*;
+    use encrypt::PaddingError;
+
+    /// A toy cipher with 4-byte blocks: XOR with the key, then rotate.
+    struct ToyCipher([u8; 4]);
+
+    impl BlockCipher for ToyCipher {
+        fn block_size(&self) -> usize {
+            4
+        }
+
+        fn encrypt_block(&self, block: &mut [u8]) {
+            for (b, k) in block.iter_mut().zip(self.0.iter()) {
+                *b ^= k;
+            }
+            block.rotate_left(1);
+        }
+
+        fn decrypt_block(&self, block: &mut [u8]) {
+            block.rotate_right(1);
+            for (b, k) in block.iter_mut().zip(self.0.iter()) {
+                *b ^= k;
+            }
+        }
+    }
+
+    #[test]
+    fn test_ecb_generic_cipher() {
+        let cipher = ToyCipher([1, 2, 3, 4]);
+        let ciphertext = ecb_encrypt(&cipher, b"abcdabcdab").unwrap();
+        assert_eq!(ciphertext.len(), 12);
+        assert_eq!(&ciphertext[..4], &ciphertext[4..8]);
+        assert_eq!(ecb_decrypt(&cipher, &ciphertext).unwrap(), b"abcdabcdab");
+
+        let cipher: &dyn BlockCipher = &cipher;
+        assert_eq!(ecb_encrypt_raw(cipher, b"abcd").unwrap(), b"````");
+        assert_eq!(ecb_decrypt_raw(cipher, b"````").unwrap(), b"abcd");
+    }
+
+    #[test]
+    fn test_ecb_errors() {
+        let cipher = ToyCipher([0; 4]);
+        assert_eq!(ecb_encrypt_raw(&cipher, b"abc"), Err(ModeError::Unaligned));
+        assert_eq!(
+            ecb_decrypt_raw(&cipher, b"abcde"),
+            Err(ModeError::Unaligned)
+        );
+        assert_eq!(ecb_encrypt_raw(&cipher, b""), Ok(vec![]));
+        assert_eq!(
+            ecb_decrypt(&cipher, b""),
+            Err(ModeError::Padding(PaddingError::InvalidLength))
+        );
+        assert_eq!(
+            ecb_decrypt(&cipher, b"abc\x05"),
+            Err(ModeError::Padding(PaddingError::PadByteTooLarge))
+        );
+    }
+
+    #[test]
+    fn test_ecb_zero_block_size() {
+        struct NullCipher;
+
+        impl BlockCipher for NullCipher {
+            fn block_size(&self) -> usize {
+                0
+            }
+
+            fn encrypt_block(&self, _: &mut [u8]) {}
+
+            fn decrypt_block(&self, _: &mut [u8]) {}
+        }
+
+        assert_eq!(
+            ecb_encrypt(&NullCipher, b"abcd"),
+            Err(ModeError::ZeroBlockSize)
+        );
+        assert_eq!(
+            ecb_encrypt_raw(&NullCipher, b"abcd"),
+            Err(ModeError::ZeroBlockSize)
+        );
+        assert_eq!(
+            ecb_decrypt_raw(&NullCipher, b""),
+            Err(ModeError::ZeroBlockSize)
+        );
+        assert_eq!(
+            ecb_decrypt(&NullCipher, b"abcd"),
+            Err(ModeError::ZeroBlockSize)
+        );
+    }
+
+    #[test]
+    fn test_ecb_block_size_too_large() {
+        struct WideCipher;
+
+        impl BlockCipher for WideCipher {
+            fn block_size(&self) -> usize {
+                256
+            }
+
+            fn encrypt_block(&self, _: &mut [u8]) {}
+
+            fn decrypt_block(&self, _: &mut [u8]) {}
+        }
+
+        assert_eq!(
+            ecb_encrypt(&WideCipher, b"abcd"),
+            Err(ModeError::BlockSizeTooLarge)
+        );
+        assert_eq!(ecb_encrypt_raw(&WideCipher, &[0; 256]).unwrap().len(), 256);
+    }
+}
@@ -0,0 +1,194 @@
+use encrypt::cbc::check_iv;
+use encrypt::{BlockCipher, ModeError};
+
+/// Encrypt `data` in CFB mode with full-block segments
+///
+/// CFB turns a block cipher into a self-synchronizing stream cipher: each
+/// segment of plaintext is XORed with the encryption of the previous
+/// ciphertext segment, or of `iv` for the first one. No padding is needed, and
+/// the last segment may be short.
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{cfb_decrypt, cfb_encrypt, Aes};
+///
+/// let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+/// let iv = [0u8; 16];
+/// let ciphertext = cfb_encrypt(&aes, &iv, b"attack at dawn").unwrap();
+///
+/// assert_eq!(ciphertext.len(), 14);
+/// assert_eq!(cfb_decrypt(&aes, &iv, &ciphertext).unwrap(), b"attack at dawn");
+/// ```
+pub fn cfb_encrypt<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    cfb(cipher, iv, data, cipher.block_size(), false)
+}
+
+/// Decrypt `data` in CFB mode with full-block segments
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long.
+pub fn cfb_decrypt<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    cfb(cipher, iv, data, cipher.block_size(), true)
+}
+
+/// Encrypt `data` in CFB mode with 8-bit segments
+///
+/// This needs one block encryption per byte, but a corrupted or lost byte of
+/// ciphertext only garbles the plaintext until it has been shifted out of the
+/// feedback register.
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long.
+pub fn cfb8_encrypt<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    cfb(cipher, iv, data, 1, false)
+}
+
+/// Decrypt `data` in CFB mode with 8-bit segments
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long.
+pub fn cfb8_decrypt<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    cfb(cipher, iv, data, 1, true)
+}
+
+/// Encrypt or decrypt `data` in OFB mode
+///
+/// The keystream is `iv` encrypted over and over, independent of the data, so
+/// the same function both encrypts and decrypts. Reusing an IV with the same
+/// key reuses the keystream.
+///
+/// # Errors
+/// Returns an error if `iv` is not one block long.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{ofb_apply, Aes};
+///
+/// let aes = Aes::new(b"YELLOW SUBMARINE").unwrap();
+/// let iv = [0u8; 16];
+/// let ciphertext = ofb_apply(&aes, &iv, b"attack at dawn").unwrap();
+///
+/// assert_eq!(ofb_apply(&aes, &iv, &ciphertext).unwrap(), b"attack at dawn");
+/// ```
+pub fn ofb_apply<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, ModeError> {
+    check_iv(iv, cipher.block_size())?;
+
+    let mut out = data.to_vec();
+    let mut register = iv.to_vec();
+    for chunk in out.chunks_mut(cipher.block_size()) {
+        cipher.encrypt_block(&mut register);
+        for (b, k) in chunk.iter_mut().zip(register.iter()) {
+            *b ^= k;
+        }
+    }
+    Ok(out)
+}
+
+/// CFB with `segment`-byte segments. The feedback register is shifted left by
+/// a segment and the ciphertext segment shifted in after each one.
+fn cfb<C: BlockCipher + ?Sized>(
+    cipher: &C,
+    iv: &[u8],
+    data: &[u8],
+    segment: usize,
+    decrypt: bool,
+) -> Result<Vec<u8>, ModeError> {
+    check_iv(iv, cipher.block_size())?;
+
+    let mut out = Vec::with_capacity(data.len());
+    let mut register = iv.to_vec();
+    for chunk in data.chunks(segment) {
+        let mut keystream = register.clone();
+        cipher.encrypt_block(&mut keystream);
+        let start = out.len();
+        out.extend(chunk.iter().zip(keystream.iter()).map(|(b, k)| b ^ k));
+
+        let ciphertext = match decrypt {
+            true => chunk,
+            false => &out[start..],
+        };
+        register.drain(..ciphertext.len());
+        register.extend_from_slice(ciphertext);
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use encrypt::feedback::*;
+    use encrypt::Aes;
+
+    fn aes() -> Aes {
+        Aes::new(b"YELLOW SUBMARINE").unwrap()
+    }
+
+    #[test]
+    fn test_feedback_round_trip() {
+        let iv: Vec<u8> = (0..16).collect();
+        for len in 0..50 {
+            let data: Vec<u8> = (0..len).collect();
+
+            let ciphertext = cfb_encrypt(&aes(), &iv, &data).unwrap();
+            assert_eq!(ciphertext.len(), data.len());
+            assert_eq!(cfb_decrypt(&aes(), &iv, &ciphertext).unwrap(), data);
+
+            let ciphertext = cfb8_encrypt(&aes(), &iv, &data).unwrap();
+            assert_eq!(ciphertext.len(), data.len());
+            assert_eq!(cfb8_decrypt(&aes(), &iv, &ciphertext).unwrap(), data);
+
+            let ciphertext = ofb_apply(&aes(), &iv, &data).unwrap();
+            assert_eq!(ofb_apply(&aes(), &iv, &ciphertext).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_cfb8_resynchronizes() {
+        let iv = [0u8; 16];
+        let data = [b'A'; 64];
+        let mut ciphertext = cfb8_encrypt(&aes(), &iv, &data).unwrap();
+        ciphertext[10] ^= 1;
+        let plaintext = cfb8_decrypt(&aes(), &iv, &ciphertext).unwrap();
+        assert_ne!(&plaintext[..27], &data[..27]);
+        assert_eq!(&plaintext[27..], &data[27..]);
+    }
+
+    #[test]
+    fn test_feedback_errors() {
+        assert_eq!(
+            cfb_encrypt(&aes(), &[0; 15], b"data"),
+            Err(ModeError::InvalidIv)
+        );
+        assert_eq!(
+            cfb8_decrypt(&aes(), &[0; 17], b"data"),
+            Err(ModeError::InvalidIv)
+        );
+        assert_eq!(ofb_apply(&aes(), &[], b"data"), Err(ModeError::InvalidIv));
+    }
+}
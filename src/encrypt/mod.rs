@@ -0,0 +1,197 @@
+mod aes;
+mod block;
+mod cbc;
+mod classical;
+mod ctr;
+mod ecb;
+mod feedback;
+mod padding;
+
+pub use self::aes::{Aes, AES_BLOCK_SIZE};
+pub use self::block::{BlockCipher, ModeError};
+pub use self::cbc::{
+    cbc_decrypt, cbc_decrypt_raw, cbc_encrypt, cbc_encrypt_raw, pcbc_decrypt, pcbc_decrypt_raw,
+    pcbc_encrypt, pcbc_encrypt_raw,
+};
+pub use self::classical::{
+    affine_decrypt, affine_encrypt, caesar_decrypt, caesar_encrypt, mod_inverse_26,
+    substitution_decrypt, substitution_encrypt, vigenere_decrypt, vigenere_encrypt,
+    SubstitutionKey,
+};
+pub use self::ctr::{CounterLayout, Ctr};
+pub use self::ecb::{ecb_decrypt, ecb_decrypt_raw, ecb_encrypt, ecb_encrypt_raw};
+pub use self::feedback::{cfb8_decrypt, cfb8_encrypt, cfb_decrypt, cfb_encrypt, ofb_apply};
+pub use self::padding::{
+    pkcs7_pad, pkcs7_unpad, AnsiX923, Iso10126, Iso7816, Padding, PaddingError, Pkcs7, ZeroPadding,
+};
+
+use conversions::HexValue;
+use std::io::{self, Read, Write};
+
+/// Size of the buffer `RepeatingKeyXor::apply_stream` reads into.
+const STREAM_BUFFER_SIZE: usize = 8192;
+
+/// Encrypt `plaintext` with repeating-key XOR, returning the ciphertext as hex
+///
+/// Use `repeating_key_xor_bytes` for binary data.
+///
+/// # Errors
+/// Returns an error if `key` is empty.
+pub fn repeating_key_xor(plaintext: &str, key: &str) -> Result<HexValue, &'static str> {
+    repeating_key_xor_bytes(plaintext.as_bytes(), key.as_bytes())
+        .map(|ciphertext| HexValue::from_bytes(&ciphertext))
+}
+
+/// XOR `data` with `key` repeated to its length
+///
+/// Repeating-key XOR is its own inverse, so this both encrypts and decrypts.
+///
+/// # Errors
+/// Returns an error if `key` is empty.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::repeating_key_xor_bytes;
+///
+/// let ciphertext = repeating_key_xor_bytes(b"\x00\x01\x02\x03", b"\xff\x0f").unwrap();
+///
+/// assert_eq!(ciphertext, vec![0xff, 0x0e, 0xfd, 0x0c]);
+/// assert_eq!(repeating_key_xor_bytes(&ciphertext, b"\xff\x0f").unwrap(), vec![0, 1, 2, 3]);
+/// assert!(repeating_key_xor_bytes(b"data", b"").is_err());
+/// ```
+pub fn repeating_key_xor_bytes(data: &[u8], key: &[u8]) -> Result<Vec<u8>, &'static str> {
+    let mut out = data.to_vec();
+    RepeatingKeyXor::new(key)?.apply(&mut out);
+    Ok(out)
+}
+
+/// Repeating-key XOR over data that arrives in pieces
+///
+/// The position in the key carries over from one call to the next, so
+/// applying it to consecutive chunks gives the same result as applying it to
+/// the whole input at once.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::RepeatingKeyXor;
+///
+/// let mut plaintext: &[u8] = b"Burning 'em, if you ain't quick and nimble";
+/// let mut ciphertext = Vec::new();
+/// RepeatingKeyXor::new(b"ICE")
+///     .unwrap()
+///     .apply_stream(&mut plaintext, &mut ciphertext)
+///     .unwrap();
+///
+/// assert_eq!(&ciphertext[..4], &[0x0b, 0x36, 0x37, 0x27]);
+/// ```
+pub struct RepeatingKeyXor {
+    key: Vec<u8>,
+    position: usize,
+}
+
+impl RepeatingKeyXor {
+    /// # Errors
+    /// Returns an error if `key` is empty.
+    pub fn new(key: &[u8]) -> Result<RepeatingKeyXor, &'static str> {
+        if key.is_empty() {
+            return Err("key must not be empty");
+        }
+        Ok(RepeatingKeyXor {
+            key: key.to_vec(),
+            position: 0,
+        })
+    }
+
+    /// XOR `data` in place, continuing from where the last call left off.
+    pub fn apply(&mut self, data: &mut [u8]) {
+        for b in data.iter_mut() {
+            *b ^= self.key[self.position];
+            self.position = (self.position + 1) % self.key.len();
+        }
+    }
+
+    /// XOR everything read from `reader` and write it to `writer`, returning
+    /// the number of bytes processed.
+    pub fn apply_stream<R: Read, W: Write>(
+        &mut self,
+        reader: &mut R,
+        writer: &mut W,
+    ) -> io::Result<u64> {
+        let mut buffer = [0u8; STREAM_BUFFER_SIZE];
+        let mut total: u64 = 0;
+        loop {
+            let n = match reader.read(&mut buffer) {
+                Ok(0) => return Ok(total),
+                Ok(n) => n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            self.apply(&mut buffer[..n]);
+            writer.write_all(&buffer[..n])?;
+            total += n as u64;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use encrypt::*;
+
+    #[test]
+    fn test_repeating_key_xor() {
+        let msg = r"Burning 'em, if you ain't quick and nimble
+I go crazy when I hear a cymbal";
+        assert_eq!(repeating_key_xor(msg, "ICE").unwrap(), HexValue::from_str("0b3637272a2b2e63622c2e69692a23693a2a3c6324202d623d63343c2a26226324272765272a282b2f20430a652e2c652a3124333a653e2b2027630c692b20283165286326302e27282f").unwrap());
+    }
+
+    #[test]
+    fn test_repeating_key_xor_empty_key() {
+        assert!(repeating_key_xor("abc", "").is_err());
+    }
+
+    #[test]
+    fn test_repeating_key_xor_bytes() {
+        let data: Vec<u8> = (0..=255).collect();
+        let ciphertext = repeating_key_xor_bytes(&data, &[0xde, 0xad, 0xbe]).unwrap();
+        assert_eq!(&ciphertext[..4], &[0xde, 0xac, 0xbc, 0xdd]);
+        assert_eq!(
+            repeating_key_xor_bytes(&ciphertext, &[0xde, 0xad, 0xbe]),
+            Ok(data)
+        );
+        assert_eq!(repeating_key_xor_bytes(b"", b"k"), Ok(vec![]));
+        assert!(repeating_key_xor_bytes(b"", b"").is_err());
+        assert!(RepeatingKeyXor::new(b"").is_err());
+    }
+
+    #[test]
+    fn test_repeating_key_xor_chunks() {
+        let data: Vec<u8> = (0..100).collect();
+        let whole = repeating_key_xor_bytes(&data, b"ICE").unwrap();
+
+        let mut xor = RepeatingKeyXor::new(b"ICE").unwrap();
+        let mut chunked = data.clone();
+        for chunk in chunked.chunks_mut(7) {
+            xor.apply(chunk);
+        }
+        assert_eq!(chunked, whole);
+    }
+
+    #[test]
+    fn test_repeating_key_xor_stream() {
+        let data: Vec<u8> = (0..20000).map(|i| (i % 251) as u8).collect();
+        let mut ciphertext = Vec::new();
+        let n = RepeatingKeyXor::new(b"YELLOW SUBMARINE")
+            .unwrap()
+            .apply_stream(&mut &data[..], &mut ciphertext)
+            .unwrap();
+        assert_eq!(n, 20000);
+        assert_eq!(
+            ciphertext,
+            repeating_key_xor_bytes(&data, b"YELLOW SUBMARINE").unwrap()
+        );
+    }
+}
@@ -0,0 +1,419 @@
+use std::collections::hash_map::RandomState;
+use std::error;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+
+/// Why padding could not be removed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddingError {
+    /// The data is empty or its length is not a multiple of the block size.
+    InvalidLength,
+    /// The last byte is zero, which PKCS#7 never produces.
+    ZeroPadByte,
+    /// The last byte is larger than the block size.
+    PadByteTooLarge,
+    /// The pad bytes are not all equal to the last byte, or for ANSI X9.23,
+    /// the filler bytes are not all zero.
+    InconsistentPadding,
+    /// ISO/IEC 7816-4 padding has no `0x80` marker in the last block.
+    MissingMarker,
+}
+
+impl PaddingError {
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            PaddingError::InvalidLength => "data length is not a multiple of the block size",
+            PaddingError::ZeroPadByte => "pad byte is zero",
+            PaddingError::PadByteTooLarge => "pad byte is larger than the block size",
+            PaddingError::InconsistentPadding => "inconsistent padding",
+            PaddingError::MissingMarker => "padding marker byte is missing",
+        }
+    }
+}
+
+impl fmt::Display for PaddingError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl error::Error for PaddingError {}
+
+/// A way of padding data to a whole number of blocks
+///
+/// Every scheme validates strictly when unpadding, so a padding oracle can be
+/// built on any of them.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{AnsiX923, Iso7816, Padding, PaddingError, Pkcs7};
+///
+/// let schemes: [&dyn Padding; 3] = [&Pkcs7, &AnsiX923, &Iso7816];
+/// for scheme in schemes.iter() {
+///     let padded = scheme.pad(b"YELLOW", 8);
+///     assert_eq!(padded.len(), 8);
+///     assert_eq!(scheme.unpad(&padded, 8), Ok(&b"YELLOW"[..]));
+/// }
+/// assert_eq!(AnsiX923.pad(b"YELLOW", 8), b"YELLOW\x00\x02");
+/// assert_eq!(AnsiX923.unpad(b"YELLOW\x01\x02", 8), Err(PaddingError::InconsistentPadding));
+/// ```
+pub trait Padding {
+    /// Pad `data` to a multiple of `block_size`.
+    ///
+    /// # Panics
+    /// Implementations panic if `block_size` is zero, and schemes that store
+    /// the pad length in a byte also panic if it is greater than 255.
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8>;
+
+    /// Remove the padding from `data`, checking that it is well formed.
+    ///
+    /// # Errors
+    /// Returns `PaddingError::InvalidLength` if the length of `data` is not a
+    /// multiple of `block_size`, and the other variants if the padding is
+    /// malformed.
+    fn unpad<'a>(&self, data: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError>;
+}
+
+/// PKCS#7 padding: every pad byte is the number of pad bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pkcs7;
+
+/// ANSI X9.23 padding: zero bytes, then the number of pad bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnsiX923;
+
+/// ISO 10126 padding: random bytes, then the number of pad bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Iso10126;
+
+/// ISO/IEC 7816-4 padding: a `0x80` byte, then zero bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Iso7816;
+
+/// Zero padding: zero bytes up to the end of the block, none if the data is
+/// already aligned
+///
+/// Trailing zero bytes of the data cannot be told apart from padding, so
+/// `unpad` removes them as well. Use it only for data that cannot end in zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroPadding;
+
+impl Padding for Pkcs7 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        pkcs7_pad(data, block_size)
+    }
+
+    fn unpad<'a>(&self, data: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        pkcs7_unpad(data, block_size)
+    }
+}
+
+impl Padding for AnsiX923 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        pad_with_length(data, block_size, |filler| {
+            for b in filler.iter_mut() {
+                *b = 0;
+            }
+        })
+    }
+
+    fn unpad<'a>(&self, data: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        let pad = pad_length(data, block_size)?;
+        if data[data.len() - pad..data.len() - 1]
+            .iter()
+            .any(|&b| b != 0)
+        {
+            return Err(PaddingError::InconsistentPadding);
+        }
+        Ok(&data[..data.len() - pad])
+    }
+}
+
+impl Padding for Iso10126 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        pad_with_length(data, block_size, fill_random)
+    }
+
+    /// The filler bytes are random, so only the length byte can be checked.
+    fn unpad<'a>(&self, data: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        let pad = pad_length(data, block_size)?;
+        Ok(&data[..data.len() - pad])
+    }
+}
+
+impl Padding for Iso7816 {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        assert!(block_size > 0, "block size must be nonzero");
+        let mut out = data.to_vec();
+        out.push(0x80);
+        out.resize(out.len().next_multiple_of(block_size), 0);
+        out
+    }
+
+    fn unpad<'a>(&self, data: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        check_length(data, block_size)?;
+        let start = data.len() - block_size;
+        match data[start..].iter().rposition(|&b| b != 0) {
+            Some(i) if data[start + i] == 0x80 => Ok(&data[..start + i]),
+            _ => Err(PaddingError::MissingMarker),
+        }
+    }
+}
+
+impl Padding for ZeroPadding {
+    fn pad(&self, data: &[u8], block_size: usize) -> Vec<u8> {
+        assert!(block_size > 0, "block size must be nonzero");
+        let mut out = data.to_vec();
+        out.resize(data.len().next_multiple_of(block_size), 0);
+        out
+    }
+
+    /// Remove up to `block_size - 1` trailing zero bytes. Empty data is
+    /// accepted, since padding empty data gives empty data.
+    fn unpad<'a>(&self, data: &'a [u8], block_size: usize) -> Result<&'a [u8], PaddingError> {
+        if block_size == 0 || !data.len().is_multiple_of(block_size) {
+            return Err(PaddingError::InvalidLength);
+        }
+        let zeros = data
+            .iter()
+            .rev()
+            .take(block_size - 1)
+            .take_while(|&&b| b == 0)
+            .count();
+        Ok(&data[..data.len() - zeros])
+    }
+}
+
+/// Pad `data` to a multiple of `block_size` with PKCS#7
+///
+/// Between 1 and `block_size` bytes are appended, each equal to the number of
+/// bytes appended, so a full block of padding is added to data that is
+/// already aligned.
+///
+/// # Panics
+/// The function panics if `block_size` is zero or greater than 255.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::pkcs7_pad;
+///
+/// assert_eq!(pkcs7_pad(b"YELLOW SUBMARINE", 20), b"YELLOW SUBMARINE\x04\x04\x04\x04");
+/// ```
+pub fn pkcs7_pad(data: &[u8], block_size: usize) -> Vec<u8> {
+    // The filler is already set to the pad length.
+    pad_with_length(data, block_size, |_| {})
+}
+
+/// Remove PKCS#7 padding from `data`, checking that it is well formed
+///
+/// # Errors
+/// Returns `PaddingError::InvalidLength` if the length of `data` is not a
+/// nonzero multiple of `block_size`, and the other variants if the padding is
+/// malformed. The checks are made in the order the variants are declared.
+///
+/// # Example
+///
+/// ```
+/// extern crate cryptopals;
+/// use cryptopals::encrypt::{pkcs7_unpad, PaddingError};
+///
+/// assert_eq!(pkcs7_unpad(b"ICE ICE BABY\x04\x04\x04\x04", 16), Ok(&b"ICE ICE BABY"[..]));
+/// assert_eq!(
+///     pkcs7_unpad(b"ICE ICE BABY\x01\x02\x03\x04", 16),
+///     Err(PaddingError::InconsistentPadding)
+/// );
+/// ```
+pub fn pkcs7_unpad(data: &[u8], block_size: usize) -> Result<&[u8], PaddingError> {
+    let pad = pad_length(data, block_size)?;
+    if data[data.len() - pad..].iter().any(|&b| b as usize != pad) {
+        return Err(PaddingError::InconsistentPadding);
+    }
+    Ok(&data[..data.len() - pad])
+}
+
+/// Append 1 to `block_size` bytes set to their count, then let `fill`
+/// overwrite all but the last of them.
+fn pad_with_length<F: FnOnce(&mut [u8])>(data: &[u8], block_size: usize, fill: F) -> Vec<u8> {
+    assert!(
+        block_size > 0 && block_size < 256,
+        "block size must be between 1 and 255"
+    );
+    let pad = block_size - data.len() % block_size;
+    let mut out = Vec::with_capacity(data.len() + pad);
+    out.extend_from_slice(data);
+    out.resize(data.len() + pad, pad as u8);
+    fill(&mut out[data.len()..data.len() + pad - 1]);
+    out
+}
+
+fn check_length(data: &[u8], block_size: usize) -> Result<(), PaddingError> {
+    match block_size == 0 || data.is_empty() || !data.len().is_multiple_of(block_size) {
+        true => Err(PaddingError::InvalidLength),
+        false => Ok(()),
+    }
+}
+
+/// Check the length of `data` and read the pad length from its last byte.
+fn pad_length(data: &[u8], block_size: usize) -> Result<usize, PaddingError> {
+    check_length(data, block_size)?;
+    let pad = data[data.len() - 1] as usize;
+    if pad == 0 {
+        return Err(PaddingError::ZeroPadByte);
+    }
+    if pad > block_size {
+        return Err(PaddingError::PadByteTooLarge);
+    }
+    Ok(pad)
+}
+
+/// Fill `buf` with unpredictable bytes from the standard library's randomly
+/// seeded hasher.
+fn fill_random(buf: &mut [u8]) {
+    let state = RandomState::new();
+    for (i, chunk) in buf.chunks_mut(8).enumerate() {
+        let mut hasher = state.build_hasher();
+        hasher.write_usize(i);
+        let bytes = hasher.finish().to_le_bytes();
+        chunk.copy_from_slice(&bytes[..chunk.len()]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use encrypt::padding::*;
+
+    #[test]
+    fn test_pkcs7_pad() {
+        assert_eq!(pkcs7_pad(b"", 4), vec![4, 4, 4, 4]);
+        assert_eq!(pkcs7_pad(b"abc", 4), b"abc\x01");
+        assert_eq!(pkcs7_pad(b"abcd", 4), b"abcd\x04\x04\x04\x04");
+        assert_eq!(pkcs7_pad(b"abcde", 1), b"abcde\x01");
+    }
+
+    #[test]
+    fn test_pkcs7_unpad() {
+        for len in 0..40 {
+            let data: Vec<u8> = (0..len).collect();
+            assert_eq!(pkcs7_unpad(&pkcs7_pad(&data, 16), 16), Ok(&data[..]));
+        }
+        assert_eq!(pkcs7_unpad(b"", 4), Err(PaddingError::InvalidLength));
+        assert_eq!(
+            pkcs7_unpad(b"abc\x01\x01", 4),
+            Err(PaddingError::InvalidLength)
+        );
+        assert_eq!(pkcs7_unpad(b"abc\x01", 0), Err(PaddingError::InvalidLength));
+        assert_eq!(pkcs7_unpad(b"abc\x00", 4), Err(PaddingError::ZeroPadByte));
+        assert_eq!(
+            pkcs7_unpad(b"abc\x05", 4),
+            Err(PaddingError::PadByteTooLarge)
+        );
+        assert_eq!(
+            pkcs7_unpad(b"ab\x01\x02", 4),
+            Err(PaddingError::InconsistentPadding)
+        );
+        assert_eq!(pkcs7_unpad(b"\x04\x04\x04\x04", 4), Ok(&b""[..]));
+    }
+
+    fn round_trip(scheme: &dyn Padding) {
+        for &block_size in [1, 8, 16].iter() {
+            for len in 0..40 {
+                let data: Vec<u8> = (1..=len).collect();
+                let padded = scheme.pad(&data, block_size);
+                assert!(padded.len().is_multiple_of(block_size));
+                assert!(padded.len() - data.len() <= block_size);
+                assert_eq!(&padded[..data.len()], &data[..]);
+                assert_eq!(scheme.unpad(&padded, block_size), Ok(&data[..]));
+            }
+        }
+    }
+
+    #[test]
+    fn test_padding_round_trip() {
+        round_trip(&Pkcs7);
+        round_trip(&AnsiX923);
+        round_trip(&Iso10126);
+        round_trip(&Iso7816);
+        round_trip(&ZeroPadding);
+    }
+
+    #[test]
+    fn test_ansi_x923() {
+        assert_eq!(AnsiX923.pad(b"abcde", 4), b"abcde\x00\x00\x03");
+        assert_eq!(AnsiX923.pad(b"abcd", 4), b"abcd\x00\x00\x00\x04");
+        assert_eq!(AnsiX923.unpad(b"abc", 4), Err(PaddingError::InvalidLength));
+        assert_eq!(
+            AnsiX923.unpad(b"abc\x00", 4),
+            Err(PaddingError::ZeroPadByte)
+        );
+        assert_eq!(
+            AnsiX923.unpad(b"abc\x05", 4),
+            Err(PaddingError::PadByteTooLarge)
+        );
+        assert_eq!(
+            AnsiX923.unpad(b"a\x00\x02\x03", 4),
+            Err(PaddingError::InconsistentPadding)
+        );
+        assert_eq!(AnsiX923.unpad(b"a\x00\x00\x03", 4), Ok(&b"a"[..]));
+    }
+
+    #[test]
+    fn test_iso10126() {
+        let padded = Iso10126.pad(&[0; 64], 128);
+        assert_eq!(padded[127], 64);
+        // 63 random filler bytes are all zero with negligible probability.
+        assert!(padded[64..127].iter().any(|&b| b != 0));
+        assert_eq!(Iso10126.unpad(b"a\x17\xff\x03", 4), Ok(&b"a"[..]));
+        assert_eq!(
+            Iso10126.unpad(b"abc\x00", 4),
+            Err(PaddingError::ZeroPadByte)
+        );
+        assert_eq!(
+            Iso10126.unpad(b"abc\x05", 4),
+            Err(PaddingError::PadByteTooLarge)
+        );
+        assert_eq!(Iso10126.unpad(b"", 4), Err(PaddingError::InvalidLength));
+    }
+
+    #[test]
+    fn test_iso7816() {
+        assert_eq!(Iso7816.pad(b"abc", 4), b"abc\x80");
+        assert_eq!(Iso7816.pad(b"abcd", 4), b"abcd\x80\x00\x00\x00");
+        assert_eq!(Iso7816.pad(b"abcd", 1000).len(), 1000);
+        assert_eq!(Iso7816.unpad(b"ab\x80\x00", 4), Ok(&b"ab"[..]));
+        assert_eq!(Iso7816.unpad(b"\x80\x00\x00\x00", 4), Ok(&b""[..]));
+        assert_eq!(
+            Iso7816.unpad(b"ab\x80\x01", 4),
+            Err(PaddingError::MissingMarker)
+        );
+        assert_eq!(
+            Iso7816.unpad(b"ab\x81\x00", 4),
+            Err(PaddingError::MissingMarker)
+        );
+        assert_eq!(
+            Iso7816.unpad(b"abc\x80\x00\x00\x00\x00", 4),
+            Err(PaddingError::MissingMarker)
+        );
+        assert_eq!(
+            Iso7816.unpad(b"ab\x80", 4),
+            Err(PaddingError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn test_zero_padding() {
+        assert_eq!(ZeroPadding.pad(b"abc", 4), b"abc\x00");
+        assert_eq!(ZeroPadding.pad(b"abcd", 4), b"abcd");
+        assert_eq!(ZeroPadding.pad(b"", 4), b"");
+        assert_eq!(ZeroPadding.unpad(b"a\x00\x00\x00", 4), Ok(&b"a"[..]));
+        assert_eq!(ZeroPadding.unpad(b"\x00\x00\x00\x00", 4), Ok(&b"\x00"[..]));
+        assert_eq!(ZeroPadding.unpad(b"", 4), Ok(&b""[..]));
+        assert_eq!(
+            ZeroPadding.unpad(b"abcde", 4),
+            Err(PaddingError::InvalidLength)
+        );
+    }
+}
@@ -2,5 +2,7 @@
 extern crate lazy_static;
 
 pub mod analysis;
+pub mod challenges;
 pub mod conversions;
+pub mod data;
 pub mod encrypt;
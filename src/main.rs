@@ -1,113 +1,108 @@
 extern crate cryptopals;
 
-mod set1 {
-    use cryptopals::analysis::english_score;
-    use cryptopals::conversions::{Base64Value, HexValue};
-    use cryptopals::encrypt::repeating_key_xor;
-    use std::fs::File;
-    use std::io::prelude::*;
+use cryptopals::challenges::{registry, Challenge, Selector};
+use cryptopals::data;
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+use std::time::Instant;
 
-    const CIPHER_CHARS: &str =
-        "abcdefghijklmnopqrsqtuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789`~!@#$%^&*()-_=+;:',<.>/?";
+const USAGE: &str = "usage: cryptopals [--data-dir DIR] [run [SELECTOR...] | list]
 
-    pub fn challenge1() {
-        // https://cryptopals.com/sets/1/challenges/1
-        if let Ok(hex) = HexValue::from_str("49276d206b696c6c696e6720796f757220627261696e206c696b65206120706f69736f6e6f7573206d757368726f6f6d") {
-            let result = hex.to_base64();
-            assert_eq!(result, Base64Value::from_str("SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtlIGEgcG9pc29ub3VzIG11c2hyb29t").unwrap());
-            println!("{} -> {}", hex, result);
-        }
-    }
+Data files are read from DIR, from $CRYPTOPALS_DATA_DIR, or from the crate's
+data directory, in that order.
 
-    pub fn challenge2() {
-        // https://cryptopals.com/sets/1/challenges/2
-        if let Ok(hex1) = HexValue::from_str("1c0111001f010100061a024b53535009181c") {
-            if let Ok(hex2) = HexValue::from_str("686974207468652062756c6c277320657965") {
-                let result = hex1.xor(&hex2);
-                assert_eq!(
-                    result,
-                    HexValue::from_str("746865206b696420646f6e277420706c6179").unwrap()
-                );
-                println!("{} ^ {} -> {}", hex1, hex2, result);
-            }
-        }
-    }
+Selectors are `all` (the default), a set such as `set1`, or a challenge such
+as `1.4`, where the number after the dot is the challenge number as on
+cryptopals.com.";
 
-    fn score_single_char_xor_decrypt(encrypted_str: &str) -> (i32, String) {
-        if let Ok(encrypted) = HexValue::from_str(encrypted_str) {
-            let winner = CIPHER_CHARS
-                .chars()
-                .map(|l| {
-                    let key_str: String =
-                        vec![HexValue::byte_to_hex(l as u8); encrypted_str.len() / 2]
-                            .into_iter()
-                            .collect();
-                    if let Ok(key) = HexValue::from_str(&key_str) {
-                        if let Ok(decrypted) = String::from_utf8(encrypted.xor(&key).bytes) {
-                            return (english_score(&decrypted), l);
-                        }
-                    }
-                    (0, l)
-                }).max_by_key(|&(s, _)| s)
-                .unwrap();
+/// Run a challenge and report whether it gave the expected result.
+fn run(challenge: &dyn Challenge) -> bool {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| challenge.run()));
+    let elapsed = start.elapsed();
 
-            let key_str: String =
-                vec![HexValue::byte_to_hex(winner.1 as u8); encrypted_str.len() / 2]
-                    .into_iter()
-                    .collect();
-            if let Ok(key) = HexValue::from_str(&key_str) {
-                if let Ok(decrypted) = String::from_utf8(encrypted.xor(&key).bytes) {
-                    return (winner.0, decrypted);
-                }
+    let (passed, detail) = match result {
+        Ok(result) => {
+            let expected = challenge.expected();
+            match result == expected {
+                true => (true, format!("{:?}", result)),
+                false => (false, format!("expected {:?}, got {:?}", expected, result)),
             }
         }
-        (0, String::from(""))
-    }
+        Err(_) => (false, String::from("panicked")),
+    };
+    println!(
+        "[{}] {}.{} {} ({:.1} ms)\n       {}",
+        if passed { "PASS" } else { "FAIL" },
+        challenge.set(),
+        challenge.number(),
+        challenge.title(),
+        elapsed.as_secs_f64() * 1000.,
+        detail
+    );
+    passed
+}
 
-    pub fn challenge3() {
-        // https://cryptopals.com/sets/1/challenges/3
-        let encrypted_str = "1b37373331363f78151b7f2b783431333d78397828372d363c78373e783a393b3736";
-        let (_, decrypted) = score_single_char_xor_decrypt(encrypted_str);
-        println!("{:?}", decrypted);
+fn main() {
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(|a| a.as_str()) == Some("--data-dir") {
+        if args.len() < 2 {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+        data::set_data_dir(args.remove(1));
+        args.remove(0);
     }
+    let challenges = registry();
 
-    pub fn challenge4() {
-        // https://cryptopals.com/sets/1/challenges/4
-        let mut f = File::open("data/4.txt").expect("file not found");
-        let mut contents = String::new();
-        f.read_to_string(&mut contents)
-            .expect("something went wrong reading the file");
-        let mut results = Vec::new();
-        for line in contents.split("\n") {
-            results.push(score_single_char_xor_decrypt(line));
+    let selectors: Vec<Selector> = match args.first().map(|a| a.as_str()) {
+        None => vec![Selector::All],
+        Some("run") if args.len() == 1 => vec![Selector::All],
+        Some("run") => args[1..]
+            .iter()
+            .map(|a| {
+                Selector::from_str(a).unwrap_or_else(|e| {
+                    eprintln!("cryptopals: {}: {}\n\n{}", a, e, USAGE);
+                    process::exit(2);
+                })
+            })
+            .collect(),
+        Some("list") => {
+            for c in challenges.iter() {
+                println!("{}.{} {}", c.set(), c.number(), c.title());
+            }
+            return;
         }
+        Some("help") | Some("--help") | Some("-h") => {
+            println!("{}", USAGE);
+            return;
+        }
+        Some(_) => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
 
-        let winner = results.iter().max_by_key(|(s, _)| s).unwrap();
-        println!("{:?}", winner.1);
+    let selected: Vec<&Box<dyn Challenge>> = challenges
+        .iter()
+        .filter(|c| selectors.iter().any(|s| s.matches(c.as_ref())))
+        .collect();
+    if selected.is_empty() {
+        let selectors: Vec<String> = selectors.iter().map(|s| s.to_string()).collect();
+        eprintln!("cryptopals: no challenges match {}", selectors.join(" "));
+        process::exit(2);
     }
 
-    pub fn challenge5() {
-        // https://cryptopals.com/sets/1/challenges/4
-        let msg = r"Burning 'em, if you ain't quick and nimble
-I go crazy when I hear a cymbal";
-        println!("{}", repeating_key_xor(msg, "ICE"));
+    let start = Instant::now();
+    let failed = selected.iter().filter(|c| !run(c.as_ref())).count();
+    println!(
+        "\n{} passed, {} failed ({:.1} ms)",
+        selected.len() - failed,
+        failed,
+        start.elapsed().as_secs_f64() * 1000.
+    );
+    if failed > 0 {
+        process::exit(1);
     }
 }
-
-fn main() {
-    println!("=== Set 1 ===");
-    println!("# Challenge 1");
-    set1::challenge1();
-
-    println!("\n# Challenge 2");
-    set1::challenge2();
-
-    println!("\n# Challenge 3");
-    set1::challenge3();
-
-    println!("\n# Challenge 4");
-    set1::challenge4();
-
-    println!("\n# Challenge 5");
-    set1::challenge5();
-}
@@ -0,0 +1,106 @@
+extern crate cryptopals;
+
+use cryptopals::conversions::HexValue;
+use cryptopals::data;
+use cryptopals::encrypt::{
+    cbc_decrypt_raw, cbc_encrypt_raw, cfb8_decrypt, cfb8_encrypt, cfb_decrypt, cfb_encrypt,
+    ecb_decrypt, ecb_decrypt_raw, ecb_encrypt, ecb_encrypt_raw, ofb_apply, Aes, CounterLayout, Ctr,
+};
+
+fn hex(value: &str) -> Vec<u8> {
+    HexValue::from_str(value).unwrap().bytes
+}
+
+/// Parse the `:`-separated hex vectors in a data file, skipping comments.
+fn load_vectors(name: &str) -> Vec<Vec<String>> {
+    data::load_string(name)
+        .unwrap()
+        .lines()
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.split(':').map(String::from).collect())
+        .collect()
+}
+
+#[test]
+fn aes_ecb_openssl_vectors() {
+    let vectors = load_vectors("aes_ecb.txt");
+    assert_eq!(vectors.len(), 27);
+    for vector in vectors {
+        let aes = Aes::new(&hex(&vector[1])).unwrap();
+        let (plaintext, ciphertext) = (hex(&vector[2]), hex(&vector[3]));
+        match vector[0].as_str() {
+            "pkcs7" => {
+                assert_eq!(ecb_encrypt(&aes, &plaintext).unwrap(), ciphertext);
+                assert_eq!(ecb_decrypt(&aes, &ciphertext).unwrap(), plaintext);
+            }
+            "none" => {
+                assert_eq!(ecb_encrypt_raw(&aes, &plaintext).unwrap(), ciphertext);
+                assert_eq!(ecb_decrypt_raw(&aes, &ciphertext).unwrap(), plaintext);
+            }
+            padding => panic!("unknown padding {}", padding),
+        }
+    }
+}
+
+#[test]
+fn aes_cbc_sp800_38a_vectors() {
+    let vectors: Vec<_> = load_vectors("sp800_38a.txt")
+        .into_iter()
+        .filter(|vector| vector[0] == "cbc")
+        .collect();
+    assert_eq!(vectors.len(), 3);
+    for vector in vectors {
+        let aes = Aes::new(&hex(&vector[1])).unwrap();
+        let (iv, plaintext, ciphertext) = (hex(&vector[2]), hex(&vector[3]), hex(&vector[4]));
+        assert_eq!(cbc_encrypt_raw(&aes, &iv, &plaintext).unwrap(), ciphertext);
+        assert_eq!(cbc_decrypt_raw(&aes, &iv, &ciphertext).unwrap(), plaintext);
+    }
+}
+
+#[test]
+fn aes_ctr_sp800_38a_vectors() {
+    let vectors: Vec<_> = load_vectors("sp800_38a.txt")
+        .into_iter()
+        .filter(|vector| vector[0] == "ctr")
+        .collect();
+    assert_eq!(vectors.len(), 3);
+    for vector in vectors {
+        let aes = Aes::new(&hex(&vector[1])).unwrap();
+        let ctr = Ctr::new(aes, CounterLayout::BigEndian(hex(&vector[2]))).unwrap();
+        let (plaintext, ciphertext) = (hex(&vector[3]), hex(&vector[4]));
+        let mut data = plaintext.clone();
+        ctr.apply(0, &mut data);
+        assert_eq!(data, ciphertext);
+        ctr.apply(0, &mut data);
+        assert_eq!(data, plaintext);
+    }
+}
+
+#[test]
+fn aes_cfb_ofb_sp800_38a_vectors() {
+    let vectors: Vec<_> = load_vectors("sp800_38a.txt")
+        .into_iter()
+        .filter(|vector| ["cfb", "cfb8", "ofb"].contains(&vector[0].as_str()))
+        .collect();
+    assert_eq!(vectors.len(), 9);
+    for vector in vectors {
+        let aes = Aes::new(&hex(&vector[1])).unwrap();
+        let (iv, plaintext, ciphertext) = (hex(&vector[2]), hex(&vector[3]), hex(&vector[4]));
+        let (encrypted, decrypted) = match vector[0].as_str() {
+            "cfb" => (
+                cfb_encrypt(&aes, &iv, &plaintext),
+                cfb_decrypt(&aes, &iv, &ciphertext),
+            ),
+            "cfb8" => (
+                cfb8_encrypt(&aes, &iv, &plaintext),
+                cfb8_decrypt(&aes, &iv, &ciphertext),
+            ),
+            _ => (
+                ofb_apply(&aes, &iv, &plaintext),
+                ofb_apply(&aes, &iv, &ciphertext),
+            ),
+        };
+        assert_eq!(encrypted.unwrap(), ciphertext);
+        assert_eq!(decrypted.unwrap(), plaintext);
+    }
+}
@@ -0,0 +1,48 @@
+extern crate cryptopals;
+
+use cryptopals::challenges::set1;
+use cryptopals::conversions::{Base64Value, HexValue};
+
+#[test]
+fn challenge1() {
+    assert_eq!(
+        set1::challenge1(),
+        Base64Value::from_str("SSdtIGtpbGxpbmcgeW91ciBicmFpbiBsaWtlIGEgcG9pc29ub3VzIG11c2hyb29t")
+            .unwrap()
+    );
+}
+
+#[test]
+fn challenge2() {
+    assert_eq!(
+        set1::challenge2(),
+        HexValue::from_str("746865206b696420646f6e277420706c6179").unwrap()
+    );
+}
+
+#[test]
+fn challenge3() {
+    let winner = set1::challenge3();
+    assert_eq!(winner.key, b'X');
+    assert_eq!(winner.plaintext, b"Cooking MC's like a pound of bacon".to_vec());
+}
+
+#[test]
+fn challenge4() {
+    let winner = set1::challenge4();
+    assert_eq!(winner.index, 170);
+    assert_eq!(winner.key, b'5');
+    assert_eq!(winner.plaintext, b"Now that the party is jumping\n".to_vec());
+}
+
+#[test]
+fn challenge5() {
+    assert_eq!(set1::challenge5(), HexValue::from_str("0b3637272a2b2e63622c2e69692a23693a2a3c6324202d623d63343c2a26226324272765272a282b2f20430a652e2c652a3124333a653e2b2027630c692b20283165286326302e27282f").unwrap());
+}
+
+#[test]
+fn challenge7() {
+    let plaintext = set1::challenge7();
+    assert!(plaintext.starts_with(b"I'm back and I'm ringin' the bell \n"));
+    assert!(plaintext.ends_with(b"we should do this.\n"));
+}
@@ -0,0 +1,31 @@
+extern crate cryptopals;
+
+use cryptopals::challenges::set2;
+use cryptopals::encrypt::PaddingError;
+
+#[test]
+fn challenge9() {
+    assert_eq!(
+        set2::challenge9(),
+        b"YELLOW SUBMARINE\x04\x04\x04\x04".to_vec()
+    );
+}
+
+#[test]
+fn challenge10() {
+    let plaintext = set2::challenge10();
+    assert!(plaintext.starts_with(b"I'm back and I'm ringin' the bell \n"));
+    assert!(plaintext.ends_with(b"we should do this.\n"));
+}
+
+#[test]
+fn challenge15() {
+    assert_eq!(
+        set2::challenge15(),
+        vec![
+            Ok(b"ICE ICE BABY".to_vec()),
+            Err(PaddingError::InconsistentPadding),
+            Err(PaddingError::InconsistentPadding),
+        ]
+    );
+}
@@ -0,0 +1,11 @@
+extern crate cryptopals;
+
+use cryptopals::challenges::set3;
+
+#[test]
+fn challenge18() {
+    assert_eq!(
+        set3::challenge18(),
+        b"Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby ".to_vec()
+    );
+}